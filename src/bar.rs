@@ -1,69 +1,197 @@
-use crossbeam::channel::{Receiver, Sender};
 use cursive::event::{Event, EventResult, Key, MouseButton, MouseEvent};
-use cursive::theme::{Effect, PaletteColor};
-use cursive::view::{View, ViewWrapper};
-use cursive::views::Button;
-use cursive::{wrap_impl, Printer, Vec2};
-use log::debug;
-
-use crate::panel::{Align, Placement};
-
-/// Trait which defines which basic action a tab bar should be able to handle
-pub trait Bar {
-    fn add_button(&mut self, tx: Sender<String>, key: &str);
-    fn remove_button(&mut self, key: &str);
-    fn swap_button(&mut self, left: &str, right: &str);
-    fn add_button_at(&mut self, tx: Sender<String>, key: &str, pos: usize);
-}
+use cursive::theme::{BaseColor, Color, ColorStyle, ColorType, Effect, PaletteColor, Style};
+use cursive::utils::lines::simple::simple_prefix;
+use cursive::utils::markup::StyledString;
+use cursive::view::View;
+use cursive::{Printer, Vec2};
+use num::clamp;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
 
-// Quick Wrapper around Views to be able to set their positon
-struct PositionWrap<T: View> {
-    view: T,
-    pub pos: Vec2,
-    pub key: String,
-}
+use crate::model::TabModel;
+use crate::panel::{Align, BorderStyle, Placement, Progress, Separator, Status, TabStyle};
 
-impl<T: View> ViewWrapper for PositionWrap<T> {
-    wrap_impl!(self.view: T);
+// A tab as drawn in the bar, the state mirrored from the model is refreshed on every sync while
+// the state only known to the bar moves along with the tab
+struct Tab<K> {
+    pub key: K,
+    // Generation of the tab in the model, the state below belongs to this instance of the tab
+    pub generation: Option<u64>,
+    pub title: String,
+    pub visible: bool,
+    pub group: Option<String>,
+    pub parent: Option<K>,
+    pub activity: bool,
+    pub collapsed: bool,
+    pub badge: Option<String>,
+    pub progress: Progress,
+    pub status: Status,
+    // Title as drawn, cut off or padded to the width of the tab
+    pub label: String,
+    pub truncated: bool,
+    // Width available to the title before stretching
+    pub space: usize,
 }
 
-impl<T: View> PositionWrap<T> {
-    pub fn new(view: T, key: String) -> Self {
+impl<K> Tab<K> {
+    pub fn new(key: K) -> Self {
         Self {
-            view,
-            pos: Vec2::zero(),
             key,
+            generation: None,
+            title: String::new(),
+            visible: true,
+            group: None,
+            parent: None,
+            activity: false,
+            collapsed: false,
+            badge: None,
+            progress: Progress::None,
+            status: Status::Normal,
+            label: String::new(),
+            truncated: false,
+            space: 0,
         }
     }
 }
 
-pub struct TabBar {
-    children: Vec<PositionWrap<Button>>,
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+// Color of a status, lighter for the active tab
+fn status_color(status: Status, active: bool) -> Option<Color> {
+    let base = match status {
+        Status::Normal => return None,
+        Status::Success => BaseColor::Green,
+        Status::Warning => BaseColor::Yellow,
+        Status::Error => BaseColor::Red,
+    };
+    Some(if active {
+        Color::Light(base)
+    } else {
+        Color::Dark(base)
+    })
+}
+
+// Display width of a text in cells
+fn width(text: &str) -> usize {
+    StyledString::plain(text).width()
+}
+
+// Fit the title of a tab into the given width, long titles are cut off with an ellipsis and
+// short ones centered
+fn fit_title(title: &str, padding: usize, space: usize) -> (String, bool) {
+    let pad = " ".repeat(padding);
+    let label = format!("{}{}{}", pad, title, pad);
+    let label_width = width(&label);
+    if label_width <= space {
+        let left = (space - label_width) / 2;
+        let right = space - label_width - left;
+        return (
+            format!("{}{}{}", " ".repeat(left), label, " ".repeat(right)),
+            false,
+        );
+    }
+    let title = if space > 2 * padding {
+        // Wide characters may leave a cell free in front of the ellipsis
+        let prefix = simple_prefix(title, space - 2 * padding - 1);
+        let fill = " ".repeat(space - 2 * padding - 1 - prefix.width);
+        format!("{}{}…{}{}", pad, &title[..prefix.length], fill, pad)
+    } else if space > 0 {
+        format!("{:<1$}", "…", space)
+    } else {
+        String::new()
+    };
+    (title, true)
+}
+
+// Place of an item along the bar, computed once per layout and shared by drawing and mouse
+// handling
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Slot {
+    // Offset of the separator in front of the item
+    start: usize,
+    // Length of the content between the separators
+    length: usize,
+}
+
+// Display state of a group of tabs
+#[derive(Default)]
+struct Group {
+    collapsed: bool,
+    color: Option<Color>,
+}
+
+// Entries drawn in the bar, a group label is placed before the first tab of each group
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Tab(usize),
+    Group(String),
+}
+
+pub struct TabBar<K> {
+    children: Vec<Tab<K>>,
     bar_size: Vec2,
     align: Align,
-    last_rendered_size: Vec2,
-    // List of accumulated sizes of prev buttons
+    // Entries drawn in the bar and their sizes, measured when the size of the bar is requested,
+    // and their places of the last layout
+    items: Vec<Item>,
     sizes: Vec<Vec2>,
+    slots: Vec<Slot>,
+    // Offset of the marker drawn when the items do not fit into the bar
+    overflow: Option<usize>,
+    groups: HashMap<String, Group>,
     placement: Placement,
     cursor: Option<usize>,
     active: Option<usize>,
-    rx: Receiver<String>,
+    // Revision of the model the tabs were last synced with
+    revision: Option<u64>,
+    // Tab chosen with the mouse or the keyboard, until the panel activates it in the model
+    selected: Option<K>,
     invalidated: bool,
+    style: TabStyle,
+    border: BorderStyle,
+    min_width: usize,
+    max_width: Option<usize>,
+    equal_width: bool,
+    padding: usize,
+    separator: Separator,
+    // Cells between tabs which are enclosed by their own separators
+    gap: usize,
+    // Tab below the pressed mouse button
+    hovered: Option<usize>,
+    // Reference point for the animation of spinners
+    created: Instant,
 }
 
-impl TabBar {
-    pub fn new(rx: Receiver<String>) -> Self {
+impl<K: Eq + Hash + Clone> TabBar<K> {
+    pub fn new() -> Self {
         Self {
             children: Vec::new(),
+            items: Vec::new(),
             sizes: Vec::new(),
+            slots: Vec::new(),
+            overflow: None,
+            groups: HashMap::new(),
             cursor: None,
             active: None,
             align: Align::Start,
             placement: Placement::HorizontalTop,
             bar_size: Vec2::zero(),
-            last_rendered_size: Vec2::zero(),
-            rx,
+            revision: None,
+            selected: None,
             invalidated: true,
+            style: TabStyle::default(),
+            border: BorderStyle::default(),
+            min_width: 0,
+            max_width: None,
+            equal_width: false,
+            padding: 1,
+            separator: Separator::default(),
+            gap: 0,
+            hovered: None,
+            created: Instant::now(),
         }
     }
 
@@ -89,195 +217,744 @@ impl TabBar {
         self.invalidated = true;
     }
 
-    fn decrement_idx(&mut self) -> EventResult {
-        if let Some(index) = self.cursor {
-            if index > 0 {
-                self.cursor = Some(index - 1);
-                self.invalidated = true;
-                EventResult::Consumed(None)
-            } else {
-                EventResult::Ignored
+    pub fn set_min_width(&mut self, width: usize) {
+        self.min_width = width;
+        self.invalidated = true;
+    }
+
+    pub fn set_max_width(&mut self, width: Option<usize>) {
+        self.max_width = width;
+        self.invalidated = true;
+    }
+
+    pub fn set_equal_width(&mut self, equal: bool) {
+        self.equal_width = equal;
+        self.invalidated = true;
+    }
+
+    pub fn set_padding(&mut self, padding: usize) {
+        self.padding = padding;
+        self.invalidated = true;
+    }
+
+    pub fn set_separator(&mut self, separator: Separator) {
+        self.separator = separator;
+        self.invalidated = true;
+    }
+
+    pub fn separator(&self) -> &Separator {
+        &self.separator
+    }
+
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+        self.invalidated = true;
+    }
+
+    fn tab<Q>(&self, key: &Q) -> Option<&Tab<K>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.children
+            .iter()
+            .find(|button| button.key.borrow() == key)
+    }
+
+    fn tab_mut<Q>(&mut self, key: &Q) -> Option<&mut Tab<K>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.children
+            .iter_mut()
+            .find(|button| button.key.borrow() == key)
+    }
+
+    pub fn is_truncated<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tab(key).is_some_and(|button| button.truncated)
+    }
+
+    pub fn cursor_key(&self) -> Option<&K> {
+        self.cursor
+            .and_then(|idx| self.children.get(idx))
+            .map(|button| &button.key)
+    }
+
+    pub fn set_group_collapsed(&mut self, group: &str, collapsed: bool) {
+        self.groups.entry(group.to_owned()).or_default().collapsed = collapsed;
+        self.fix_cursor();
+        self.invalidated = true;
+    }
+
+    pub fn is_group_collapsed(&self, group: &str) -> bool {
+        self.groups.get(group).is_some_and(|group| group.collapsed)
+    }
+
+    pub fn set_group_color(&mut self, group: &str, color: Color) {
+        self.groups.entry(group.to_owned()).or_default().color = Some(color);
+        self.invalidated = true;
+    }
+
+    pub fn set_badge<Q>(&mut self, key: &Q, badge: Option<&str>)
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if let Some(button) = self.tab_mut(key) {
+            button.badge = badge.map(|badge| badge.to_owned());
+        }
+        self.invalidated = true;
+    }
+
+    pub fn badge<Q>(&self, key: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tab(key).and_then(|button| button.badge.as_deref())
+    }
+
+    pub fn set_progress<Q>(&mut self, key: &Q, progress: Progress)
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if let Some(button) = self.tab_mut(key) {
+            button.progress = progress;
+        }
+        self.invalidated = true;
+    }
+
+    pub fn progress<Q>(&self, key: &Q) -> Progress
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tab(key)
+            .map_or(Progress::None, |button| button.progress)
+    }
+
+    pub fn set_status<Q>(&mut self, key: &Q, status: Status)
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if let Some(button) = self.tab_mut(key) {
+            button.status = status;
+        }
+        self.invalidated = true;
+    }
+
+    pub fn status<Q>(&self, key: &Q) -> Status
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tab(key).map_or(Status::Normal, |button| button.status)
+    }
+
+    // Text shown for the progress of a tab, including its trailing space
+    fn progress_label(&self, idx: usize) -> String {
+        match self.children[idx].progress {
+            Progress::None => String::new(),
+            Progress::Spinner => {
+                let frame = self.created.elapsed().as_millis() / SPINNER_INTERVAL.as_millis();
+                format!("{} ", SPINNER[frame as usize % SPINNER.len()])
             }
-        } else {
-            EventResult::Ignored
+            Progress::Percent(percent) => format!("{}% ", percent.min(100)),
         }
     }
 
-    fn increment_idx(&mut self) -> EventResult {
-        if let Some(index) = self.cursor {
-            if (index + 1) < self.children.len() {
-                self.cursor = Some(index + 1);
-                self.invalidated = true;
-                EventResult::Consumed(None)
-            } else {
-                EventResult::Ignored
+    pub fn set_tab_collapsed<Q>(&mut self, key: &Q, collapsed: bool)
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if let Some(button) = self.tab_mut(key) {
+            button.collapsed = collapsed;
+        }
+        self.fix_cursor();
+        self.invalidated = true;
+    }
+
+    pub fn is_tab_collapsed<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tab(key).is_some_and(|button| button.collapsed)
+    }
+
+    // Whether the button at the given index is drawn, it may be hidden or inside a collapsed group or tree
+    fn is_shown(&self, idx: usize) -> bool {
+        let child = &self.children[idx];
+        child.visible
+            && !child
+                .group
+                .as_ref()
+                .is_some_and(|group| self.is_group_collapsed(group))
+            && !self
+                .ancestors(idx)
+                .any(|parent| self.children[parent].collapsed)
+    }
+
+    fn parent(&self, idx: usize) -> Option<usize> {
+        let parent = self.children[idx].parent.as_ref()?;
+        self.children
+            .iter()
+            .position(|button| button.key == *parent)
+    }
+
+    fn ancestors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.parent(idx), move |parent| self.parent(*parent))
+    }
+
+    // Number of visible tabs below the given one in the tree
+    fn descendant_count(&self, idx: usize) -> usize {
+        (0..self.children.len())
+            .filter(|child| {
+                self.children[*child].visible && self.ancestors(*child).any(|a| a == idx)
+            })
+            .count()
+    }
+
+    // Tree guides drawn in front of a tab in vertical placements, e.g. "│ └─"
+    fn tree_prefix(&self, idx: usize) -> String {
+        if self.placement == Placement::HorizontalTop
+            || self.placement == Placement::HorizontalBottom
+        {
+            return String::new();
+        }
+        let mut guides = Vec::new();
+        let mut current = idx;
+        while let Some(parent) = self.parent(current) {
+            // Whether another sibling follows further down in the bar
+            let last = !(current + 1..self.children.len())
+                .any(|next| self.is_shown(next) && self.parent(next) == Some(parent));
+            guides.push(match (current == idx, last) {
+                (true, true) => "└─",
+                (true, false) => "├─",
+                (false, true) => "  ",
+                (false, false) => "│ ",
+            });
+            current = parent;
+        }
+        guides.reverse();
+        guides.concat()
+    }
+
+    // Marks a collapsed tab with the number of tabs folded into it
+    fn tree_suffix(&self, idx: usize) -> String {
+        if self.children[idx].collapsed {
+            match self.descendant_count(idx) {
+                0 => String::new(),
+                count => format!("(+{}) ", count),
             }
         } else {
-            EventResult::Ignored
+            String::new()
         }
     }
-}
 
-impl Bar for TabBar {
-    fn add_button(&mut self, tx: Sender<String>, key: &str) {
-        let k = key.to_owned();
-        let button = Button::new_raw(format!(" {} ", key), move |_| {
-            debug!("send {}", k);
-            match tx.send(k.clone()) {
-                Ok(_) => {}
-                Err(err) => {
-                    debug!("button could not send key: {:?}", err);
+    // Moves the cursor to the closest shown button if the current one vanished
+    fn fix_cursor(&mut self) {
+        if let Some(pos) = self.cursor {
+            if pos >= self.children.len() || !self.is_shown(pos) {
+                self.cursor = (pos + 1..self.children.len())
+                    .chain((0..pos.min(self.children.len())).rev())
+                    .find(|idx| self.is_shown(*idx));
+            }
+        }
+    }
+
+    fn build_items(&self) -> Vec<Item> {
+        let mut items = Vec::new();
+        let mut last_group = None;
+        for (idx, child) in self.children.iter().enumerate() {
+            if !child.visible {
+                continue;
+            }
+            let group = child.group.as_deref();
+            if group != last_group {
+                if let Some(name) = group {
+                    items.push(Item::Group(name.to_owned()));
                 }
+                last_group = group;
             }
-        });
-        self.children
-            .push(PositionWrap::new(button, key.to_owned()));
-        self.cursor = Some(self.children.len() - 1);
-        self.active = Some(self.children.len() - 1);
-        self.invalidated = true;
+            if self.is_shown(idx) {
+                items.push(Item::Tab(idx));
+            }
+        }
+        items
     }
 
-    fn remove_button(&mut self, key: &str) {
-        if let Some(pos) = self
-            .children
-            .iter()
-            .enumerate()
-            .filter_map(
-                |(pos, button)| {
-                    if button.key == *key {
-                        Some(pos)
-                    } else {
-                        None
+    fn group_label(&self, group: &str) -> String {
+        if self.is_group_collapsed(group) {
+            let count = self
+                .children
+                .iter()
+                .filter(|child| child.visible && child.group.as_deref() == Some(group))
+                .count();
+            format!(" {} (+{}) ", group, count)
+        } else {
+            format!(" {} ", group)
+        }
+    }
+
+    // A collapsed group or tree counts as active if it contains the active tab
+    fn is_item_active(&self, item: &Item) -> bool {
+        match item {
+            Item::Tab(idx) => {
+                self.active == Some(*idx)
+                    || self.children[*idx].collapsed
+                        && self
+                            .active
+                            .is_some_and(|active| self.ancestors(active).any(|a| a == *idx))
+            }
+            Item::Group(name) => {
+                self.is_group_collapsed(name)
+                    && self
+                        .active
+                        .is_some_and(|active| self.children[active].group.as_ref() == Some(name))
+            }
+        }
+    }
+
+    // Like activeness, activity of tabs folded into a collapsed group or tree is shown on it
+    fn item_has_activity(&self, item: &Item) -> bool {
+        match item {
+            Item::Tab(idx) => {
+                self.children[*idx].activity
+                    || self.children[*idx].collapsed
+                        && (0..self.children.len()).any(|child| {
+                            self.children[child].activity
+                                && self.ancestors(child).any(|a| a == *idx)
+                        })
+            }
+            Item::Group(name) => {
+                self.is_group_collapsed(name)
+                    && self
+                        .children
+                        .iter()
+                        .any(|child| child.activity && child.group.as_ref() == Some(name))
+            }
+        }
+    }
+
+    // Most severe status of an item, including the tabs hidden behind it
+    fn item_status(&self, item: &Item) -> Status {
+        match item {
+            Item::Tab(idx) if self.children[*idx].collapsed => (0..self.children.len())
+                .filter(|child| *child == *idx || self.ancestors(*child).any(|a| a == *idx))
+                .map(|child| self.children[child].status)
+                .max()
+                .unwrap_or_default(),
+            Item::Tab(idx) => self.children[*idx].status,
+            Item::Group(name) if self.is_group_collapsed(name) => self
+                .children
+                .iter()
+                .filter(|child| child.group.as_ref() == Some(name))
+                .map(|child| child.status)
+                .max()
+                .unwrap_or_default(),
+            Item::Group(_) => Status::Normal,
+        }
+    }
+
+    pub fn set_style(&mut self, style: TabStyle) {
+        self.style = style;
+    }
+
+    pub fn style(&self) -> &TabStyle {
+        &self.style
+    }
+
+    pub fn set_border_style(&mut self, border: BorderStyle) {
+        self.border = border;
+    }
+
+    pub fn border_style(&self) -> BorderStyle {
+        self.border
+    }
+
+    // Style of an item in its current state
+    fn item_style(&self, item: &Item) -> Style {
+        let active = self.is_item_active(item);
+        let style = if active {
+            self.style.active
+        } else {
+            self.style.inactive
+        };
+        if let Some(color) = status_color(self.item_status(item), active) {
+            style.combine(ColorStyle::front(color))
+        } else if !active && self.item_has_activity(item) {
+            // Draw attention to tabs which changed in the background
+            style.combine(ColorStyle::front(PaletteColor::Highlight))
+        } else {
+            style
+        }
+    }
+
+    // Style of a tab title, the cursor and the mouse take precedence over the state of the tab
+    fn label_style(&self, idx: usize, style: Style, printer: &Printer) -> Style {
+        let style = if !printer.enabled {
+            self.style.disabled
+        } else if printer.focused {
+            self.style.focused
+        } else {
+            style
+        };
+        if self.hovered == Some(idx) {
+            style.combine(self.style.hovered)
+        } else {
+            style
+        }
+    }
+
+    fn draw_item(&self, printer: &Printer, item: &Item, style: Style) {
+        match item {
+            Item::Tab(idx) => {
+                let prefix = self.tree_prefix(*idx);
+                let offset = width(&prefix);
+                let label = &self.children[*idx].label;
+                printer.print((0, 0), &prefix);
+                printer.with_style(self.label_style(*idx, style, printer), |printer| {
+                    printer.print((offset, 0), label)
+                });
+                // Decorations share the effects of the title but keep the surrounding colors
+                printer.with_style(Style::from(style.effects), |printer| {
+                    let mut offset = offset + width(label);
+                    let progress = self.progress_label(*idx);
+                    printer.print((offset, 0), &progress);
+                    offset += width(&progress);
+                    if let Some(badge) = &self.children[*idx].badge {
+                        printer.with_color(
+                            ColorStyle::new(PaletteColor::HighlightText, PaletteColor::Highlight),
+                            |printer| printer.print((offset, 0), badge),
+                        );
+                        offset += width(badge);
+                        printer.print((offset, 0), " ");
+                        offset += 1;
                     }
-                },
-            )
-            .next()
-        {
-            if let Some(idx) = self.cursor {
-                if idx == pos {
-                    self.cursor = None;
-                    self.active = None;
+                    printer.print((offset, 0), &self.tree_suffix(*idx));
+                });
+            }
+            Item::Group(name) => {
+                let color: ColorType =
+                    status_color(self.item_status(item), self.is_item_active(item))
+                        .or_else(|| self.groups.get(name).and_then(|group| group.color))
+                        .map_or(PaletteColor::Tertiary.into(), |color| color.into());
+                printer.with_color(ColorStyle::front(color), |printer| {
+                    printer.with_effect(Effect::Bold, |printer| {
+                        printer.print((0, 0), &self.group_label(name))
+                    })
+                });
+            }
+        }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.placement == Placement::HorizontalTop || self.placement == Placement::HorizontalBottom
+    }
+
+    // Width of a separator along the bar, vertical bars use a whole row for it
+    fn separator_width(&self) -> usize {
+        match &self.separator {
+            Separator::None => 0,
+            Separator::Text(normal, active) if self.is_horizontal() => {
+                width(normal).max(width(active))
+            }
+            _ => 1,
+        }
+    }
+
+    // Whether every item is enclosed by its own separators instead of sharing them
+    fn is_apart(&self) -> bool {
+        self.gap > 0 || matches!(self.align, Align::SpaceBetween)
+    }
+
+    // Separator between two items, the strong variant is used next to the active tab
+    fn separator_glyph(&self, strong: bool) -> &str {
+        let glyphs = self.border.glyphs();
+        match &self.separator {
+            Separator::Border if self.is_horizontal() => glyphs.separator[strong as usize],
+            Separator::Border => glyphs.divider[strong as usize],
+            Separator::Text(normal, active) => {
+                if strong {
+                    active
+                } else {
+                    normal
                 }
             }
-            self.children.remove(pos);
+            Separator::None => "",
         }
-        self.invalidated = true;
     }
 
-    fn swap_button(&mut self, first: &str, second: &str) {
-        let pos: Vec<usize> = self
-            .children
+    // Separator in front of or after an item without a neighbour to share it with
+    fn end_glyph(&self, open: bool, strong: bool) -> &str {
+        let glyphs = self.border.glyphs();
+        let ends = match (self.is_horizontal(), open) {
+            (true, true) => glyphs.open,
+            (true, false) => glyphs.close,
+            (false, true) => glyphs.open_vertical,
+            (false, false) => glyphs.close_vertical,
+        };
+        match &self.separator {
+            Separator::Border => ends[strong as usize],
+            _ => self.separator_glyph(strong),
+        }
+    }
+
+    // Lengths of the items along the bar
+    fn lengths(&self) -> Vec<usize> {
+        self.sizes
             .iter()
-            .enumerate()
-            .filter_map(|(pos, button)| {
-                if button.key == *first || button.key == *second {
-                    Some(pos)
-                } else {
-                    None
+            .map(|size| if self.is_horizontal() { size.x } else { size.y })
+            .collect()
+    }
+
+    // Distance from the end of an item to the start of the next one
+    fn step(&self) -> usize {
+        if self.is_apart() {
+            2 * self.separator_width() + self.gap
+        } else {
+            self.separator_width()
+        }
+    }
+
+    // Length of all items and the separators between and around them
+    fn content_length(&self) -> usize {
+        let lengths = self.lengths();
+        lengths.iter().sum::<usize>()
+            + lengths.len().saturating_sub(1) * self.step()
+            + 2 * self.separator_width()
+    }
+
+    // Place every item along a bar of the given length
+    fn place(&self, container: usize) -> Vec<Slot> {
+        let lengths = self.lengths();
+        let count = lengths.len();
+        let step = self.step();
+        let content = self.content_length();
+        let mut slots = Vec::with_capacity(count);
+        match self.align {
+            Align::Stretch => {
+                let tabs = self
+                    .items
+                    .iter()
+                    .filter(|item| matches!(item, Item::Tab(_)))
+                    .count()
+                    .max(1);
+                let free = container.saturating_sub(content);
+                let mut start = 0;
+                let mut tab = 0;
+                for (item, length) in self.items.iter().zip(lengths) {
+                    // Group labels keep their size
+                    let extra = match item {
+                        Item::Tab(_) => {
+                            tab += 1;
+                            free / tabs + usize::from(tab <= free % tabs)
+                        }
+                        Item::Group(_) => 0,
+                    };
+                    slots.push(Slot {
+                        start,
+                        length: length + extra,
+                    });
+                    start += length + extra + step;
                 }
-            })
+            }
+            Align::SpaceBetween if count > 1 => {
+                // The free space lies between the items
+                let gaps = count - 1;
+                let free = container.saturating_sub(content);
+                let mut start = 0;
+                for (idx, length) in lengths.into_iter().enumerate() {
+                    slots.push(Slot { start, length });
+                    start += length + step + free / gaps + usize::from(idx < free % gaps);
+                }
+            }
+            align => {
+                let mut start = align.get_offset(content, container);
+                for length in lengths {
+                    slots.push(Slot { start, length });
+                    start += length + step;
+                }
+            }
+        }
+        slots
+    }
+
+    // Offset of the overflow marker on the last cell of the bar, if the content of the placed
+    // items reaches beyond it
+    fn overflow(&self, container: usize) -> Option<usize> {
+        let separator = self.separator_width();
+        self.slots
+            .last()
+            .filter(|slot| slot.start + separator + slot.length > container)
+            .and(container.checked_sub(1))
+    }
+
+    // Mirror the order and state of the tabs in the model, the state only known to the bar is kept
+    // for every tab which is still there
+    pub fn sync(&mut self, model: &TabModel<K>) {
+        if self.revision == Some(model.revision()) {
+            return;
+        }
+        self.revision = Some(model.revision());
+        let key = |idx: Option<usize>| {
+            idx.and_then(|idx| self.children.get(idx))
+                .map(|tab| tab.key.clone())
+        };
+        let cursor = key(self.cursor);
+        let hovered = key(self.hovered);
+        let mut previous: HashMap<K, Tab<K>> = self
+            .children
+            .drain(..)
+            .map(|tab| (tab.key.clone(), tab))
             .collect();
-        if let [pos1, pos2] = pos[..] {
-            let child2 = self.children.remove(pos2);
-            let child1 = self.children.remove(pos1);
-            self.children.insert(pos1, child2);
-            self.children.insert(pos2, child1);
+        let mut added = false;
+        for key in model.order() {
+            // A tab replacing a former one with the same id starts out without its state
+            let generation = model.generation(key);
+            let mut tab = previous
+                .remove(key)
+                .filter(|tab| tab.generation == generation)
+                .unwrap_or_else(|| {
+                    added = true;
+                    Tab::new(key.clone())
+                });
+            tab.generation = generation;
+            tab.title = model.title(key).unwrap_or_default().to_owned();
+            tab.visible = model.is_visible(key);
+            tab.group = model.group(key).map(|group| group.to_owned());
+            tab.parent = model.parent(key).cloned();
+            tab.activity = model.has_activity(key);
+            self.children.push(tab);
         }
+        let children = &self.children;
+        let position =
+            |key: Option<&K>| key.and_then(|key| children.iter().position(|tab| tab.key == *key));
+        self.active = position(model.active());
+        // New tabs are activated, so the cursor follows them
+        self.cursor = if added {
+            self.active
+        } else {
+            position(cursor.as_ref()).or(self.active)
+        };
+        self.hovered = position(hovered.as_ref());
+        self.fix_cursor();
+        // Items refer to the tabs by position, places are only known again after the next layout
+        self.items = self.build_items();
+        self.sizes.clear();
+        self.slots.clear();
+        self.overflow = None;
         self.invalidated = true;
     }
 
-    fn add_button_at(&mut self, tx: Sender<String>, key: &str, pos: usize) {
-        let k = key.to_owned();
-        let button = Button::new_raw(format!(" {} ", key), move |_| {
-            debug!("send {}", k);
-            match tx.send(k.clone()) {
-                Ok(_) => {}
-                Err(err) => {
-                    debug!("button could not send key: {:?}", err);
-                }
-            }
-        });
-        self.cursor = Some(pos);
-        self.active = Some(pos);
-        self.children
-            .insert(pos, PositionWrap::new(button, key.to_owned()));
+    // Activates the tab at the given index in the bar, the panel applies it to the model
+    fn select(&mut self, idx: usize) -> EventResult {
+        self.cursor = Some(idx);
+        self.active = Some(idx);
+        self.selected = Some(self.children[idx].key.clone());
         self.invalidated = true;
+        EventResult::Consumed(None)
+    }
+
+    // Takes the tab selected since the last call
+    pub fn take_selected(&mut self) -> Option<K> {
+        self.selected.take()
+    }
+
+    fn fold_cursor(&mut self, collapse: bool) -> EventResult {
+        match self.cursor {
+            Some(idx)
+                if self.children[idx].collapsed != collapse && self.descendant_count(idx) > 0 =>
+            {
+                self.children[idx].collapsed = collapse;
+                self.invalidated = true;
+                EventResult::Consumed(None)
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn decrement_idx(&mut self) -> EventResult {
+        if let Some(index) = self.cursor {
+            if let Some(prev) = (0..index).rev().find(|idx| self.is_shown(*idx)) {
+                self.cursor = Some(prev);
+                self.invalidated = true;
+                EventResult::Consumed(None)
+            } else {
+                EventResult::Ignored
+            }
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn increment_idx(&mut self) -> EventResult {
+        if let Some(index) = self.cursor {
+            if let Some(next) = (index + 1..self.children.len()).find(|idx| self.is_shown(*idx)) {
+                self.cursor = Some(next);
+                self.invalidated = true;
+                EventResult::Consumed(None)
+            } else {
+                EventResult::Ignored
+            }
+        } else {
+            EventResult::Ignored
+        }
     }
 }
 
-impl View for TabBar {
+impl<K: Eq + Hash + Clone + Send + Sync + 'static> View for TabBar<K> {
     fn draw(&self, printer: &Printer) {
+        let items = &self.items;
+        let glyphs = self.border.glyphs();
+        let separator = self.separator_width();
+        // Items with their own separators on both sides
+        let apart = self.is_apart();
         match self.placement {
             Placement::HorizontalBottom | Placement::HorizontalTop => {
                 // First draw the complete horizontal line
-                printer.print_hline((0, 0), printer.size.x, "─");
-                // Spacing for padding & crop end
-                let inner_printer = printer
-                    // Alignment
-                    .offset((
-                        self.align
-                            .get_offset(self.bar_size.x + self.children.len() + 1, printer.size.x),
-                        0,
-                    ));
-                for (idx, child) in self.children.iter().enumerate() {
-                    // There is no chainable api...
-                    let mut rel_sizes = self.sizes.clone();
-                    rel_sizes.truncate(idx);
-                    let mut print = inner_printer
-                        .offset(
-                            rel_sizes
-                                .iter()
-                                .fold(Vec2::new(0, 0), |acc, x| acc.stack_horizontal(x))
-                                .keep_x(),
-                        )
-                        // Spacing for first character
-                        .offset((idx, 0))
-                        .cropped({
-                            if idx == 0 || idx == self.children.len() - 1 {
-                                self.sizes[idx].stack_horizontal(&Vec2::new(2, 1))
-                            } else {
-                                self.sizes[idx].stack_horizontal(&Vec2::new(1, 1))
-                            }
-                        });
-                    let mut theme = printer.theme.clone();
-
-                    if !self.active.map_or(false, |active| idx == active) {
-                        let color = theme.palette[PaletteColor::TitleSecondary];
-                        theme.palette[PaletteColor::Primary] = color;
-                    } else {
-                        let color = theme.palette[PaletteColor::TitlePrimary];
-                        theme.palette[PaletteColor::Primary] = color;
-                    }
+                printer.with_style(self.style.border, |printer| {
+                    printer.print_hline((0, 0), printer.size.x, glyphs.horizontal)
+                });
+                for (idx, (item, slot)) in items.iter().zip(&self.slots).enumerate() {
+                    let Slot { start, length } = *slot;
+                    // Including the separators on both sides
+                    let mut print = printer
+                        .offset((start, 0))
+                        .cropped((length + 2 * separator, 1));
+                    let active = self.is_item_active(item);
+                    let style = self.item_style(item);
 
-                    if let Some(focus) = self.cursor {
-                        print = print.focused(focus == idx);
+                    if let (Some(focus), Item::Tab(child)) = (self.cursor, item) {
+                        print = print.focused(focus == *child);
                     }
 
-                    print.with_theme(&theme, |printer| {
-                        if idx > 0 {
-                            if self.active.map_or(false, |active| idx == active)
-                                || self.active.map_or(false, |active| active == (idx - 1))
-                            {
-                                printer.print((0, 0), "┃")
-                            } else {
-                                printer.print((0, 0), "│");
-                            }
-                        } else if self.active.map_or(false, |active| idx == active) {
-                            printer.print((0, 0), "┨")
+                    print.with_style(self.style.separator, |printer| {
+                        if idx > 0 && !apart {
+                            let strong = active || self.is_item_active(&items[idx - 1]);
+                            printer.print((0, 0), self.separator_glyph(strong));
                         } else {
-                            printer.print((0, 0), "┤");
+                            printer.print((0, 0), self.end_glyph(true, active));
                         }
-                        printer.with_effect(Effect::Bold, |printer| {
-                            child.draw(&printer.offset((1, 0)))
-                        });
-                        if idx == self.children.len() - 1 {
-                            if self.active.map_or(false, |active| idx == active) {
-                                printer.offset((1, 0)).print(self.sizes[idx].keep_x(), "┠");
-                            } else {
-                                printer.offset((1, 0)).print(self.sizes[idx].keep_x(), "├");
-                            }
+                        if idx == items.len() - 1 || apart {
+                            printer.print((separator + length, 0), self.end_glyph(false, active));
                         }
                     });
+                    self.draw_item(&print.offset((separator, 0)), item, style);
+                }
+                if let Some(offset) = self.overflow {
+                    printer.with_style(self.style.separator, |printer| {
+                        printer.print((offset, 0), glyphs.overflow[0])
+                    });
                 }
             }
             Placement::VerticalLeft | Placement::VerticalRight => {
@@ -286,29 +963,14 @@ impl View for TabBar {
                     Placement::VerticalLeft => printer.size.x - 1,
                     _ => 0,
                 };
-                printer.print_vline((horizontal_offset, 0), printer.size.y, "│");
-                // Spacing for padding & crop end
-                let inner_printer = printer
-                    // Alignment
-                    .offset((
-                        0,
-                        self.align
-                            .get_offset(self.bar_size.y + self.children.len() + 1, printer.size.y),
-                    ));
-                for (idx, child) in self.children.iter().enumerate() {
-                    // There is no chainable api...
-                    let mut rel_sizes = self.sizes.clone();
-                    rel_sizes.truncate(idx);
-                    let mut print = inner_printer
-                        // Move the printer to the position of the child, respecting the height of all previous ones
-                        .offset(
-                            rel_sizes
-                                .iter()
-                                .fold(Vec2::new(0, 0), |acc, x| acc.stack_vertical(x))
-                                .keep_y(),
-                        )
-                        // Spacing for first character of the current one and all previous ones
-                        .offset((0, idx))
+                printer.with_style(self.style.border, |printer| {
+                    printer.print_vline((horizontal_offset, 0), printer.size.y, glyphs.vertical)
+                });
+                // Only border separators are joined with the vertical line
+                let joined = self.separator == Separator::Border;
+                for (idx, (item, slot)) in items.iter().zip(&self.slots).enumerate() {
+                    let Slot { start, length } = *slot;
+                    let mut print = printer
                         // Offset so that the right side when aligned to the left is on the panel border
                         .offset((
                             if self.placement == Placement::VerticalLeft {
@@ -316,61 +978,60 @@ impl View for TabBar {
                             } else {
                                 0
                             },
-                            0,
+                            start,
                         ))
                         // Crop to size including the delimiters
-                        .cropped({
-                            if idx == 0 || idx == self.children.len() - 1 {
-                                self.sizes[idx].stack_vertical(&Vec2::new(1, 2))
-                            } else {
-                                self.sizes[idx].stack_vertical(&Vec2::new(1, 1))
-                            }
-                        });
-                    let mut theme = printer.theme.clone();
+                        .cropped((self.sizes[idx].x, length + 2 * separator));
+                    let active = self.is_item_active(item);
+                    let style = self.item_style(item);
 
-                    if !self.active.map_or(false, |active| idx == active) {
-                        let color = theme.palette[PaletteColor::TitleSecondary];
-                        theme.palette[PaletteColor::Primary] = color;
-                    } else {
-                        let color = theme.palette[PaletteColor::TitlePrimary];
-                        theme.palette[PaletteColor::Primary] = color;
-                    }
-
-                    if let Some(focus) = self.cursor {
-                        print = print.focused(focus == idx);
+                    if let (Some(focus), Item::Tab(child)) = (self.cursor, item) {
+                        print = print.focused(focus == *child);
                     }
-                    print.with_theme(&theme, |printer| {
-                        if idx > 0 {
-                            if self.active.map_or(false, |active| idx == active)
-                                || self.active.map_or(false, |active| active == (idx - 1))
-                            {
-                                printer.print_hline((0, 0), printer.size.x, "━");
+                    if separator > 0 {
+                        print.with_style(self.style.separator, |printer| {
+                            if idx > 0 && !apart {
+                                let strong = active || self.is_item_active(&items[idx - 1]);
+                                printer.print_hline(
+                                    (0, 0),
+                                    printer.size.x,
+                                    self.separator_glyph(strong),
+                                );
                             } else {
-                                printer.print_hline((0, 0), printer.size.x, "─");
+                                printer.print_hline(
+                                    (0, 0),
+                                    printer.size.x,
+                                    self.separator_glyph(active),
+                                );
+                                if joined {
+                                    printer.print(
+                                        (horizontal_offset, 0),
+                                        self.end_glyph(true, active),
+                                    );
+                                }
+                            }
+                            if idx == items.len() - 1 || apart {
+                                printer.print_hline(
+                                    (0, length + 1),
+                                    printer.size.x,
+                                    self.separator_glyph(active),
+                                );
+                                if joined {
+                                    printer.print(
+                                        (horizontal_offset, length + 1),
+                                        self.end_glyph(false, active),
+                                    );
+                                }
                             }
-                        } else if self.active.map_or(false, |active| idx == active) {
-                            printer.print_hline((0, 0), printer.size.x, "━");
-                            printer.print((horizontal_offset, 0), "┷")
-                        } else {
-                            printer.print_hline((0, 0), printer.size.x, "─");
-                            printer.print((horizontal_offset, 0), "┴");
-                        }
-                        printer.with_effect(Effect::Bold, |printer| {
-                            child.draw(&printer.offset((0, 1)))
                         });
-                        if idx == self.children.len() - 1 {
-                            let (delim, connector) =
-                                if self.active.map_or(false, |active| idx == active) {
-                                    ("━", "┯")
-                                } else {
-                                    ("─", "┬")
-                                };
-                            printer.print_hline((0, printer.size.y - 1), printer.size.x, delim);
-                            printer.print(
-                                self.sizes[idx].keep_y() + Vec2::new(horizontal_offset, 1),
-                                connector,
-                            );
-                        }
+                    }
+                    // Stretched items keep their title in the middle
+                    let middle = (length - self.sizes[idx].y) / 2;
+                    self.draw_item(&print.offset((0, separator + middle)), item, style);
+                }
+                if let Some(offset) = self.overflow {
+                    printer.with_style(self.style.separator, |printer| {
+                        printer.print((horizontal_offset, offset), glyphs.overflow[1])
                     });
                 }
             }
@@ -379,68 +1040,98 @@ impl View for TabBar {
 
     fn layout(&mut self, vec: Vec2) {
         self.invalidated = false;
-        for (child, size) in self.children.iter_mut().zip(self.sizes.iter()) {
-            child.layout(*size);
+        let container = if self.is_horizontal() { vec.x } else { vec.y };
+        self.slots = self.place(container);
+        self.overflow = self.overflow(container);
+        if let (Align::Stretch, true) = (self.align, self.is_horizontal()) {
+            // Stretched tabs have more room for their titles
+            for ((item, size), slot) in self.items.iter().zip(&self.sizes).zip(&self.slots) {
+                if let Item::Tab(idx) = item {
+                    let child = &mut self.children[*idx];
+                    let (title, truncated) = fit_title(
+                        &child.title,
+                        self.padding,
+                        child.space + slot.length - size.x,
+                    );
+                    child.label = title;
+                    child.truncated = truncated;
+                }
+            }
         }
-        self.last_rendered_size = vec;
     }
 
     fn needs_relayout(&self) -> bool {
         self.invalidated
     }
 
-    fn required_size(&mut self, cst: Vec2) -> Vec2 {
-        while self.rx.len() > 1 {
-            // Discard old messages
-            // This may happen if more than one view gets added to before the event loop of cursive gets started, resulting
-            // in an incorrect start state
-            match self.rx.try_recv() {
-                Ok(_) => debug!("Got too many requests dropping some..."),
-                Err(e) => debug!("Other side got dropped {:?}, ignoring this error", e),
-            }
+    fn required_size(&mut self, _cst: Vec2) -> Vec2 {
+        self.items = self.build_items();
+        self.sizes.clear();
+        if self.items.is_empty() {
+            return Vec2::new(1, 1);
         }
-        if let Ok(new_active) = self.rx.try_recv() {
-            self.invalidated = true;
-            for (idx, child) in self.children.iter().enumerate() {
-                if new_active == child.key {
-                    self.active = Some(idx);
-                }
+        // Width of the decorations and the full width of every tab
+        let mut widths = HashMap::new();
+        for item in &self.items {
+            if let Item::Tab(idx) = item {
+                let badge = self.children[*idx]
+                    .badge
+                    .as_ref()
+                    .map_or(0, |badge| width(badge) + 1);
+                let decoration = width(&self.tree_prefix(*idx))
+                    + width(&self.progress_label(*idx))
+                    + badge
+                    + width(&self.tree_suffix(*idx));
+                let full = decoration + width(&self.children[*idx].title) + 2 * self.padding;
+                let max = self.max_width.unwrap_or(usize::MAX).max(self.min_width);
+                widths.insert(*idx, (decoration, clamp(full, self.min_width, max)));
             }
         }
-        self.sizes.clear();
-        let sizes = &mut self.sizes;
-        let placement = self.placement;
-        if self.children.is_empty() {
-            return Vec2::new(1, 1);
+        if self.equal_width {
+            let widest = widths.values().map(|(_, full)| *full).max().unwrap_or(0);
+            widths.values_mut().for_each(|(_, full)| *full = widest);
         }
-        let total_size = self
-            .children
-            .iter_mut()
-            .fold(Vec2::zero(), |mut acc, child| {
-                let size = child.required_size(cst);
-                match placement {
-                    Placement::HorizontalBottom | Placement::HorizontalTop => {
-                        acc = acc.stack_horizontal(&size);
-                    }
-                    Placement::VerticalLeft | Placement::VerticalRight => {
-                        acc = acc.stack_vertical(&size);
-                    }
+        let mut total_size = Vec2::zero();
+        for item in &self.items {
+            let size = match item {
+                Item::Tab(idx) => {
+                    let (decoration, full) = widths[idx];
+                    let space = full.saturating_sub(decoration);
+                    let (title, truncated) =
+                        fit_title(&self.children[*idx].title, self.padding, space);
+                    self.children[*idx].label = title;
+                    self.children[*idx].truncated = truncated;
+                    self.children[*idx].space = space;
+                    Vec2::new(decoration + space, 1)
                 }
-                child.pos = acc;
-                sizes.push(size);
-                acc
-            });
+                Item::Group(name) => Vec2::new(width(&self.group_label(name)), 1),
+            };
+            match self.placement {
+                Placement::HorizontalBottom | Placement::HorizontalTop => {
+                    total_size = total_size.stack_horizontal(&size);
+                }
+                Placement::VerticalLeft | Placement::VerticalRight => {
+                    total_size = total_size.stack_vertical(&size);
+                }
+            }
+            self.sizes.push(size);
+        }
         // Total size of bar
         self.bar_size = total_size;
         // Return max width and maximum height of child
         // We need the max size of every side here so try again
+        // Only custom separators and gaps may need more room than that
+        let mut length = if self.is_horizontal() {
+            total_size.x * 2
+        } else {
+            total_size.y * 2
+        };
+        if self.gap > 0 || !matches!(self.separator, Separator::Border) {
+            length = length.max(self.content_length());
+        }
         match self.placement {
-            Placement::HorizontalTop | Placement::HorizontalBottom => {
-                (total_size.x * 2, total_size.y).into()
-            }
-            Placement::VerticalLeft | Placement::VerticalRight => {
-                (total_size.x, total_size.y * 2).into()
-            }
+            Placement::HorizontalTop | Placement::HorizontalBottom => (length, total_size.y).into(),
+            Placement::VerticalLeft | Placement::VerticalRight => (total_size.x, length).into(),
         }
     }
 
@@ -451,56 +1142,67 @@ impl View for TabBar {
             event,
         } = evt
         {
-            for (idx, child) in self.children.iter().peekable().enumerate() {
-                if position.checked_sub(offset).is_some()
-                    && (match self.placement {
-                        Placement::HorizontalBottom | Placement::HorizontalTop => {
-                            child.pos
-                                + Vec2::new(idx + 1, 0)
-                                + Vec2::new(
-                                    self.align.get_offset(
-                                        // Length of buttons and delimiting characters
-                                        self.bar_size.x + self.children.len() + 1,
-                                        self.last_rendered_size.x,
-                                    ),
-                                    0,
-                                )
-                        }
-                        Placement::VerticalLeft | Placement::VerticalRight => {
-                            child.pos
-                                + Vec2::new(0, idx + 1)
-                                + Vec2::new(
-                                    0,
-                                    self.align.get_offset(
-                                        // Length of buttons and delimiting characters
-                                        self.bar_size.y + self.children.len() + 1,
-                                        self.last_rendered_size.y,
-                                    ),
-                                )
-                        }
-                    })
-                    .fits(position - offset)
-                {
+            self.hovered = None;
+            let horizontal = self.is_horizontal();
+            let separator = self.separator_width();
+            for (item, slot) in self.items.iter().zip(&self.slots) {
+                let Slot { start, length } = *slot;
+                // An item covers the separator in front of it and its content
+                let end = start + separator + length;
+                let hit = position.checked_sub(offset).is_some_and(|pos| {
+                    // The overflow marker covers the items below it
+                    if horizontal {
+                        pos.y <= self.bar_size.y
+                            && start <= pos.x
+                            && pos.x < end
+                            && self.overflow != Some(pos.x)
+                    } else {
+                        pos.x <= self.bar_size.x
+                            && start <= pos.y
+                            && pos.y < end
+                            && self.overflow != Some(pos.y)
+                    }
+                });
+                if hit {
+                    if let (MouseEvent::Press(_) | MouseEvent::Hold(_), Item::Tab(idx)) =
+                        (event, item)
+                    {
+                        self.hovered = Some(*idx);
+                    }
                     if let MouseEvent::Release(MouseButton::Left) = event {
                         self.invalidated = true;
-                        self.cursor = Some(idx);
-                        return self.children[idx].on_event(Event::Key(Key::Enter));
+                        match item.clone() {
+                            Item::Tab(idx) => return self.select(idx),
+                            Item::Group(name) => {
+                                // Clicking a group label toggles between the chip and its tabs
+                                let collapsed = self.is_group_collapsed(&name);
+                                self.set_group_collapsed(&name, !collapsed);
+                                return EventResult::Consumed(None);
+                            }
+                        }
                     }
                 }
             }
         }
 
-        if let Some(focus) = self.cursor {
-            let pos = self.children[focus].pos;
-
-            if let EventResult::Consumed(any) = self.children[focus].on_event(evt.relativized(pos))
+        match evt {
+            Event::Key(Key::Enter) => match self.cursor {
+                Some(idx) => self.select(idx),
+                None => EventResult::Ignored,
+            },
+            // Fold and unfold children of the tab below the cursor in vertical placements
+            Event::Key(Key::Left)
+                if self.placement == Placement::VerticalLeft
+                    || self.placement == Placement::VerticalRight =>
             {
-                self.invalidated = true;
-                return EventResult::Consumed(any);
+                self.fold_cursor(true)
+            }
+            Event::Key(Key::Right)
+                if self.placement == Placement::VerticalLeft
+                    || self.placement == Placement::VerticalRight =>
+            {
+                self.fold_cursor(false)
             }
-        }
-
-        match evt {
             Event::Key(Key::Left)
                 if self.placement == Placement::HorizontalBottom
                     || self.placement == Placement::HorizontalTop =>
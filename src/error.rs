@@ -1,12 +1,41 @@
+/// Error returned when there is no tab with the given id.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct IdNotFound {
-    pub id: String,
+pub struct IdNotFound<K = String> {
+    pub id: K,
 }
 
-impl std::error::Error for IdNotFound {}
+impl<K: std::fmt::Debug + std::fmt::Display> std::error::Error for IdNotFound<K> {}
 
-impl std::fmt::Display for IdNotFound {
+impl<K: std::fmt::Display> std::fmt::Display for IdNotFound<K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Id not found: {}", self.id)
     }
 }
+
+/// Error returned when the view of a tab is requested with a concrete type.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ViewAccessError<K = String> {
+    /// There is no tab with this id.
+    IdNotFound(K),
+    /// The view of the tab with this id is not of the requested type.
+    TypeMismatch(K),
+    /// The view of the tab with this id is enclosed in a `NamedView`, which only allows to
+    /// borrow it mutably, e.g. with `call_on_tab`.
+    NamedView(K),
+}
+
+impl<K: std::fmt::Debug + std::fmt::Display> std::error::Error for ViewAccessError<K> {}
+
+impl<K: std::fmt::Display> std::fmt::Display for ViewAccessError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewAccessError::IdNotFound(id) => write!(f, "Id not found: {}", id),
+            ViewAccessError::TypeMismatch(id) => {
+                write!(f, "View of tab {} has a different type", id)
+            }
+            ViewAccessError::NamedView(id) => {
+                write!(f, "View of tab {} can only be borrowed mutably", id)
+            }
+        }
+    }
+}
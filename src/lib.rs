@@ -38,37 +38,89 @@
 //! ```
 extern crate cursive_core as cursive;
 
-use crossbeam::channel::{Receiver, Sender};
 use cursive::direction::Direction;
 use cursive::event::{AnyCb, Event, EventResult};
 use cursive::view::{CannotFocus, Selector, View, ViewNotFound};
 use cursive::views::NamedView;
 use cursive::{Printer, Rect, Vec2};
-use log::debug;
+use std::any::Any;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
 
 mod bar;
 mod error;
+mod model;
 mod panel;
 
 // Reexports
-use bar::{Bar, TabBar};
-pub use panel::{Align, Placement, TabPanel};
+use bar::TabBar;
+pub use error::{IdNotFound, ViewAccessError};
+pub use model::TabModel;
+pub use panel::{Align, BorderStyle, Placement, Progress, Separator, Status, TabPanel, TabStyle};
+
+// Comparator of the ids and titles of two tabs
+type TabOrdering<K> = Box<dyn Fn((&K, &str), (&K, &str)) -> Ordering + Send + Sync>;
+
+/// Decides what happens to the children of a tab when it is removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChildPolicy {
+    /// Close all children, and their children, together with the removed tab.
+    Close,
+    /// Keep the children and move them to the parent of the removed tab.
+    Reparent,
+}
+
 /// Main struct which manages views
-pub struct TabView {
-    current_id: Option<String>,
-    // Version 0.6 changes this to only contain NamedViews, in the map this remains the same type though
-    // as NamedViews cannot be sized properly due to their enclosed view trait object
-    map: HashMap<String, Box<dyn View>>,
-    key_order: Vec<String>,
-    bar_rx: Option<Receiver<String>>,
-    active_key_tx: Option<Sender<String>>,
+///
+/// Tabs are identified by keys of type `K`, which defaults to `String` with the name of the view
+/// as key. Any other type implementing `Eq`, `Hash` and `Clone` can be used instead, e.g. an enum
+/// of the panes of an application, with the titles shown in the bar being set separately.
+///
+/// # Example
+/// ```
+/// # use cursive::{view::Nameable, views::TextView};
+/// # use cursive_tabs::TabView;
+/// #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// enum Pane {
+///     Logs,
+///     Hosts,
+/// }
+///
+/// let mut tabs = TabView::default()
+///     .with_keyed_tab(Pane::Logs, TextView::new("No logs yet").with_name("Logs"))
+///     .with_keyed_tab(Pane::Hosts, TextView::new("No hosts yet").with_name("Hosts"));
+/// tabs.set_active_tab(&Pane::Logs).expect("Id not found");
+/// tabs.set_tab_title(&Pane::Hosts, "Hosts (42)").expect("Id not found");
+/// assert_eq!(tabs.tab_title(&Pane::Hosts), Some("Hosts (42)"));
+/// ```
+pub struct TabView<K = String> {
+    // Tabs may hold any view, named or not
+    map: HashMap<K, Box<dyn View>>,
+    model: TabModel<K>,
+    // Last known `needs_relayout` state of every view, used to detect activity on inactive tabs
+    relayout_state: HashMap<K, bool>,
+    // Data attached to tabs by the user
+    data: HashMap<K, Box<dyn Any + Send + Sync>>,
+    // Keeps the tabs sorted when new ones are added
+    sorting: Option<TabOrdering<K>>,
+    poll_activity: bool,
     invalidated: bool,
 }
 
-impl Default for TabView {
+impl<K> Default for TabView<K> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            map: HashMap::new(),
+            model: TabModel::default(),
+            relayout_state: HashMap::new(),
+            data: HashMap::new(),
+            sorting: None,
+            poll_activity: true,
+            invalidated: true,
+        }
     }
 }
 
@@ -88,19 +140,53 @@ impl TabView {
     /// #  // siv.run();
     /// ```
     pub fn new() -> Self {
-        Self {
-            current_id: None,
-            map: HashMap::new(),
-            key_order: Vec::new(),
-            bar_rx: None,
-            active_key_tx: None,
-            invalidated: true,
-        }
+        Self::default()
+    }
+
+    /// Add a new tab to the tab view, the name of the view is used as its id.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    pub fn add_tab<T: View>(&mut self, view: NamedView<T>) {
+        self.add_keyed_tab(view.name().to_owned(), view);
+    }
+
+    /// Add a new tab to the tab view.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab<T: View>(mut self, view: NamedView<T>) -> Self {
+        self.add_tab(view);
+        self
+    }
+
+    /// Add a new tab at a given position, the name of the view is used as its id.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is designed to not fail, if the given position is greater than the number of current tabs, it simply will be appended.
+    pub fn add_tab_at<T: View>(&mut self, view: NamedView<T>, pos: usize) {
+        self.add_keyed_tab_at(view.name().to_owned(), view, pos);
+    }
+
+    /// Add a new tab at a given position.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// It is designed to be fail-safe, if the given position is greater than the number of current tabs, it simply will be appended.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab_at<T: View>(mut self, view: NamedView<T>, pos: usize) -> Self {
+        self.add_tab_at(view, pos);
+        self
+    }
+}
+
+impl<K: Eq + Hash + Clone> TabView<K> {
+    /// Returns the order and state of the tabs.
+    pub fn model(&self) -> &TabModel<K> {
+        &self.model
     }
 
     /// Returns the currently active tab Id.
-    pub fn active_tab(&self) -> Option<&str> {
-        self.current_id.as_deref()
+    pub fn active_tab(&self) -> Option<&K> {
+        self.model.active()
     }
 
     /// Returns a reference to the underlying view.
@@ -111,7 +197,7 @@ impl TabView {
 
     /// Returns a mutable reference to the underlying view.
     pub fn active_view_mut(&mut self) -> Option<&mut dyn View> {
-        if let Some(k) = self.current_id.as_ref() {
+        if let Some(k) = self.model.active() {
             self.map.get_mut(k).map(|v| &mut **v)
         } else {
             None
@@ -127,193 +213,775 @@ impl TabView {
         self.map.values_mut().map(|v| &mut **v).collect()
     }
 
-    /// Set the currently active (visible) tab.
-    /// If the tab id is not known, an error is returned and no action is performed.
-    pub fn set_active_tab(&mut self, id: &str) -> Result<(), error::IdNotFound> {
-        if self.map.contains_key(id) {
-            if let Some(sender) = &self.active_key_tx {
-                match sender.send(id.to_owned()) {
-                    Ok(_) => {}
-                    Err(e) => debug!(
-                        "error occured while trying to send new active key to sender: {}",
-                        e
-                    ),
-                }
-            }
-            self.current_id = Some(id.to_owned());
-            self.invalidated = true;
-            Ok(())
-        } else {
-            Err(error::IdNotFound { id: id.to_owned() })
+    /// Returns an iterator over the id, the title and the view of every tab, in tab order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &str, &dyn View)> {
+        self.model.order().iter().filter_map(move |key| {
+            let title = self.model.title(key).unwrap_or_default();
+            self.map.get(key).map(|view| (key, title, &**view))
+        })
+    }
+
+    /// Returns an iterator over the id, the title and the mutable view of every tab, in tab order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &str, &mut dyn View)> {
+        let model = &self.model;
+        let mut views: HashMap<&K, &mut Box<dyn View>> = self.map.iter_mut().collect();
+        model.order().iter().filter_map(move |key| {
+            let title = model.title(key).unwrap_or_default();
+            views.remove(key).map(|view| (key, title, &mut **view))
+        })
+    }
+
+    /// Returns the number of tabs, including hidden ones.
+    pub fn len(&self) -> usize {
+        self.model.len()
+    }
+
+    /// Returns whether there are no tabs at all.
+    pub fn is_empty(&self) -> bool {
+        self.model.is_empty()
+    }
+
+    /// Returns whether a tab with the given id exists.
+    pub fn contains<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.contains(id)
+    }
+
+    /// Returns the position of the given tab in the tab order.
+    pub fn index_of<Q>(&self, id: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.index_of(id)
+    }
+
+    /// Returns the id of the tab at the given position in the tab order.
+    pub fn id_at(&self, idx: usize) -> Option<&K> {
+        self.model.order().get(idx)
+    }
+
+    /// Calls the callback with the view of the given tab, downcast to its concrete type.
+    /// Views added as `NamedView<V>` are unwrapped, so `V` is the type of the enclosed view.
+    ///
+    /// If the tab id is not known or the view has another type, an error is returned and the
+    /// callback is not called.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabView;
+    /// let mut tabs = TabView::new().with_tab(TextView::new("Connecting...").with_name("Logs"));
+    /// tabs.call_on_tab("Logs", |view: &mut TextView| view.set_content("Connected"))
+    ///     .expect("Logs is a TextView");
+    /// ```
+    pub fn call_on_tab<V, R, Q>(
+        &mut self,
+        id: &Q,
+        cb: impl FnOnce(&mut V) -> R,
+    ) -> Result<R, error::ViewAccessError<K>>
+    where
+        V: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let view = self
+            .map
+            .get_mut(id)
+            .ok_or_else(|| error::ViewAccessError::IdNotFound(id.to_owned()))?;
+        if let Some(view) = view.downcast_mut::<V>() {
+            return Ok(cb(view));
+        }
+        match view.downcast_mut::<NamedView<V>>() {
+            Some(named) => Ok(cb(&mut named.get_mut())),
+            None => Err(error::ViewAccessError::TypeMismatch(id.to_owned())),
         }
     }
 
-    /// Set the currently active (visible) tab.
+    /// Returns the view of the given tab, downcast to its concrete type.
+    /// If the tab id is not known or the view has another type, an error is returned.
+    ///
+    /// The view enclosed in a `NamedView` can only be borrowed mutably, for tabs added with
+    /// `add_tab` this returns `ViewAccessError::NamedView`. Use `call_on_tab` for them instead,
+    /// or request the `NamedView` itself.
+    pub fn get_tab<V, Q>(&self, id: &Q) -> Result<&V, error::ViewAccessError<K>>
+    where
+        V: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let view = self
+            .map
+            .get(id)
+            .ok_or_else(|| error::ViewAccessError::IdNotFound(id.to_owned()))?;
+        if let Some(view) = view.downcast_ref::<V>() {
+            return Ok(view);
+        }
+        match view.downcast_ref::<NamedView<V>>() {
+            Some(_) => Err(error::ViewAccessError::NamedView(id.to_owned())),
+            None => Err(error::ViewAccessError::TypeMismatch(id.to_owned())),
+        }
+    }
+
+    /// Set the currently active (visible) tab, a hidden tab is shown again.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_active_tab<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.set_active(id)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Set the currently active (visible) tab, a hidden tab is shown again.
     /// If the tab id is not known, an error is returned and no action is performed.
     ///
     /// This is the consumable variant.
-    pub fn with_active_tab(mut self, id: &str) -> Result<Self, Self> {
+    #[allow(clippy::result_large_err)]
+    pub fn with_active_tab<Q>(mut self, id: &Q) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
         match self.set_active_tab(id) {
             Ok(_) => Ok(self),
             Err(_) => Err(self),
         }
     }
 
-    /// Add a new tab to the tab view.
+    /// Add a new tab with the given id to the tab view, the name of the view is used as its title.
     /// The new tab will be set active and will be the visible tab for this tab view.
-    pub fn add_tab<T: View>(&mut self, view: NamedView<T>) {
-        let id = view.name().to_owned();
-        self.map.insert(id.clone(), Box::new(view));
-        self.key_order.push(id.clone());
-        self.current_id = Some(id);
+    pub fn add_keyed_tab<T: View>(&mut self, id: K, view: NamedView<T>) {
+        let title = view.name().to_owned();
+        self.insert_tab(id, title, Box::new(view), None);
     }
 
-    /// Add a new tab to the tab view.
+    /// Add a new tab with the given id to the tab view, the name of the view is used as its title.
     /// The new tab will be set active and will be the visible tab for this tab view.
     ///
     /// This is the consumable variant.
-    pub fn with_tab<T: View>(mut self, view: NamedView<T>) -> Self {
-        self.add_tab(view);
+    pub fn with_keyed_tab<T: View>(mut self, id: K, view: NamedView<T>) -> Self {
+        self.add_keyed_tab(id, view);
         self
     }
 
-    /// Add a new tab at a given position.
+    /// Add a new tab with the given id at a given position, the name of the view is used as its title.
     /// The new tab will be set active and will be the visible tab for this tab view.
     ///
     /// This is designed to not fail, if the given position is greater than the number of current tabs, it simply will be appended.
-    pub fn add_tab_at<T: View>(&mut self, view: NamedView<T>, pos: usize) {
-        let id = view.name().to_owned();
-        self.map.insert(id.clone(), Box::new(view));
-        if let Some(sender) = &self.active_key_tx {
-            match sender.send(id.clone()) {
-                Ok(_) => {}
-                Err(send_err) => debug!(
-                    "Could not send new key to receiver in TabBar, has it been dropped? {}",
-                    send_err
-                ),
-            }
+    pub fn add_keyed_tab_at<T: View>(&mut self, id: K, view: NamedView<T>, pos: usize) {
+        let title = view.name().to_owned();
+        self.insert_tab(id, title, Box::new(view), Some(pos));
+    }
+
+    /// Add a new tab with the given id at a given position, the name of the view is used as its title.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_keyed_tab_at<T: View>(mut self, id: K, view: NamedView<T>, pos: usize) -> Self {
+        self.add_keyed_tab_at(id, view, pos);
+        self
+    }
+
+    /// Add a new tab with the given id and title to the tab view, the view does not need to be
+    /// named and the id needs no textual representation.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    pub fn add_tab_with_title<T: View>(&mut self, id: K, title: impl Into<String>, view: T) {
+        self.insert_tab(id, title.into(), Box::new(view), None);
+    }
+
+    /// Add a new tab with the given id and title to the tab view, the view does not need to be named.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab_with_title<T: View>(
+        mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+    ) -> Self {
+        self.add_tab_with_title(id, title, view);
+        self
+    }
+
+    /// Add a new tab with the given id and title at a given position, the view does not need to
+    /// be named and the id needs no textual representation.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is designed to not fail, if the given position is greater than the number of current tabs, it simply will be appended.
+    pub fn add_tab_with_title_at<T: View>(
+        &mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+        pos: usize,
+    ) {
+        self.insert_tab(id, title.into(), Box::new(view), Some(pos));
+    }
+
+    /// Add a new tab with the given id and title at a given position, the view does not need to be named.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab_with_title_at<T: View>(
+        mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+        pos: usize,
+    ) -> Self {
+        self.add_tab_with_title_at(id, title, view, pos);
+        self
+    }
+
+    /// Add a new tab with the given id to the tab view, the view does not need to be named.
+    /// The id is used as title of the tab, see `set_tab_title` to show another one.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    pub fn add_tab_with_id<T: View>(&mut self, id: K, view: T)
+    where
+        K: Display,
+    {
+        let title = id.to_string();
+        self.add_tab_with_title(id, title, view);
+    }
+
+    /// Add a new tab with the given id to the tab view, the view does not need to be named.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab_with_id<T: View>(mut self, id: K, view: T) -> Self
+    where
+        K: Display,
+    {
+        self.add_tab_with_id(id, view);
+        self
+    }
+
+    /// Add a new tab with the given id at a given position, the view does not need to be named.
+    /// The id is used as title of the tab, see `set_tab_title` to show another one.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is designed to not fail, if the given position is greater than the number of current tabs, it simply will be appended.
+    pub fn add_tab_with_id_at<T: View>(&mut self, id: K, view: T, pos: usize)
+    where
+        K: Display,
+    {
+        let title = id.to_string();
+        self.add_tab_with_title_at(id, title, view, pos);
+    }
+
+    /// Add a new tab with the given id at a given position, the view does not need to be named.
+    /// The new tab will be set active and will be the visible tab for this tab view.
+    ///
+    /// This is the consumable variant.
+    pub fn with_tab_with_id_at<T: View>(mut self, id: K, view: T, pos: usize) -> Self
+    where
+        K: Display,
+    {
+        self.add_tab_with_id_at(id, view, pos);
+        self
+    }
+
+    fn insert_tab(&mut self, id: K, title: String, view: Box<dyn View>, pos: Option<usize>) {
+        // Sorted tabs ignore the given position, the new tab goes in front of the first greater one
+        let pos = match &self.sorting {
+            Some(compare) => self
+                .model
+                .order()
+                .iter()
+                .filter(|key| **key != id)
+                .position(|key| {
+                    let other = (key, self.model.title(key).unwrap_or_default());
+                    compare((&id, &title), other) == Ordering::Less
+                })
+                .or(Some(self.model.len())),
+            None => pos,
+        };
+        match pos {
+            Some(pos) => self.model.add_at(id.clone(), title, pos),
+            None => self.model.add(id.clone(), title),
         }
-        self.current_id = Some(id.clone());
-        if self.key_order.len() > pos {
-            self.key_order.insert(pos, id)
-        } else {
-            self.key_order.push(id);
+        // A new tab with the id of an existing one does not inherit its data
+        self.data.remove(&id);
+        self.map.insert(id, view);
+        self.invalidated = true;
+    }
+
+    /// Set the title shown for the tab with the given id, which is the name of its view by default.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_tab_title<Q>(
+        &mut self,
+        id: &Q,
+        title: impl Into<String>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.set_title(id, title)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Returns the title shown for the tab with the given id.
+    pub fn tab_title<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.title(id)
+    }
+
+    /// Attach data of any type to the tab with the given id, e.g. the file or connection it shows.
+    /// The data stays with the tab when it is moved and is dropped once the tab is removed.
+    /// Data attached before is replaced.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabView;
+    /// # use std::path::PathBuf;
+    /// let mut tabs = TabView::new().with_tab(TextView::new("fn main() {}").with_name("main.rs"));
+    /// tabs.set_tab_data("main.rs", PathBuf::from("src/main.rs"))
+    ///     .expect("Id not found");
+    /// assert_eq!(
+    ///     tabs.tab_data::<PathBuf, _>("main.rs"),
+    ///     Some(&PathBuf::from("src/main.rs"))
+    /// );
+    /// ```
+    pub fn set_tab_data<T, Q>(&mut self, id: &Q, data: T) -> Result<(), error::IdNotFound<K>>
+    where
+        T: Any + Send + Sync,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if !self.model.contains(id) {
+            return Err(error::IdNotFound { id: id.to_owned() });
         }
+        self.data.insert(id.to_owned(), Box::new(data));
+        Ok(())
     }
 
-    /// Add a new tab at a given position.
-    /// The new tab will be set active and will be the visible tab for this tab view.
+    /// Returns the data attached to the tab with the given id.
+    /// `None` is returned if there is no data or it is not of the requested type.
+    pub fn tab_data<T, Q>(&self, id: &Q) -> Option<&T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.data.get(id).and_then(|data| data.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the data attached to the tab with the given id.
+    /// `None` is returned if there is no data or it is not of the requested type.
+    pub fn tab_data_mut<T, Q>(&mut self, id: &Q) -> Option<&mut T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.data.get_mut(id).and_then(|data| data.downcast_mut())
+    }
+
+    /// Removes the data attached to the tab with the given id and returns it.
+    /// If the data is not of the requested type, it stays attached and `None` is returned.
+    pub fn take_tab_data<T, Q>(&mut self, id: &Q) -> Option<T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.data.get(id)?.is::<T>() {
+            return None;
+        }
+        self.data
+            .remove(id)
+            .and_then(|data| data.downcast().ok())
+            .map(|data| *data)
+    }
+
+    /// Replace the view of the tab with the given id, the tab keeps its position, title, data and
+    /// active state. Returns the view which has been replaced.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn replace_tab<T, Q>(
+        &mut self,
+        id: &Q,
+        view: T,
+    ) -> Result<Box<dyn View>, error::IdNotFound<K>>
+    where
+        T: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let old = self
+            .map
+            .get_mut(id)
+            .ok_or_else(|| error::IdNotFound { id: id.to_owned() })?;
+        let old = std::mem::replace(old, Box::new(view));
+        // The new view starts without a known relayout state, to not count as activity
+        self.relayout_state.remove(id);
+        self.invalidated = true;
+        Ok(old)
+    }
+
+    /// Sort the tabs with a comparator of their ids and titles, tabs comparing equal keep their
+    /// order. Children stay behind their parent and groups stay together, the active tab stays active.
     ///
-    /// It is designed to be fail-safe, if the given position is greater than the number of current tabs, it simply will be appended.
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabView;
+    /// let mut tabs = TabView::new()
+    ///     .with_tab(TextView::new("Second").with_name("b"))
+    ///     .with_tab(TextView::new("First").with_name("a"));
+    /// tabs.sort_tabs_by(|(fst, _), (snd, _)| fst.cmp(snd));
+    /// assert_eq!(tabs.tab_order(), vec!["a", "b"]);
+    /// ```
+    pub fn sort_tabs_by<F>(&mut self, compare: F)
+    where
+        F: FnMut((&K, &str), (&K, &str)) -> Ordering,
+    {
+        self.model.sort_by(compare);
+        self.invalidated = true;
+    }
+
+    /// Sort the tabs by a key extracted from their ids and titles, tabs with equal keys keep their order.
+    pub fn sort_tabs_by_key<T, F>(&mut self, mut key: F)
+    where
+        T: Ord,
+        F: FnMut((&K, &str)) -> T,
+    {
+        self.sort_tabs_by(|fst, snd| key(fst).cmp(&key(snd)));
+    }
+
+    /// Sort the tabs with the given comparator and keep them sorted, new tabs are inserted at
+    /// their sorted position instead of the end or the given position.
+    /// Tabs moved afterwards, e.g. with `swap_tabs`, are not sorted again.
+    pub fn set_keep_sorted_by<F>(&mut self, compare: F)
+    where
+        F: Fn((&K, &str), (&K, &str)) -> Ordering + Send + Sync + 'static,
+    {
+        self.sort_tabs_by(&compare);
+        self.sorting = Some(Box::new(compare));
+    }
+
+    /// Sort the tabs with the given comparator and keep them sorted.
     ///
     /// This is the consumable variant.
-    pub fn with_tab_at<T: View>(mut self, view: NamedView<T>, pos: usize) -> Self {
-        self.add_tab_at(view, pos);
+    pub fn with_keep_sorted_by<F>(mut self, compare: F) -> Self
+    where
+        F: Fn((&K, &str), (&K, &str)) -> Ordering + Send + Sync + 'static,
+    {
+        self.set_keep_sorted_by(compare);
         self
     }
 
+    /// Stop keeping the tabs sorted, new tabs are added at the end or the given position again.
+    pub fn clear_keep_sorted(&mut self) {
+        self.sorting = None;
+    }
+
+    /// Returns whether new tabs are inserted at their sorted position.
+    pub fn is_kept_sorted(&self) -> bool {
+        self.sorting.is_some()
+    }
+
     /// Swap the tabs position.
     /// If one of the given key cannot be found, then no operation is performed.
-    pub fn swap_tabs(&mut self, fst: &str, snd: &str) {
-        let mut fst_pos: Option<usize> = None;
-        let mut snd_pos: Option<usize> = None;
-        for (pos, key) in self.tab_order().into_iter().enumerate() {
-            match key {
-                val if val == *fst => fst_pos = Some(pos),
-                val if val == *snd => snd_pos = Some(pos),
-                _ => {}
-            }
-        }
-        if let (Some(fst_pos), Some(snd_pos)) = (fst_pos, snd_pos) {
-            if let Some(cur) = self.current_id.as_ref() {
-                if self.active_key_tx.is_some() && (fst == cur || snd == cur) {
-                    self.active_key_tx
-                        .as_mut()
-                        .unwrap()
-                        .send(cur.to_owned())
-                        .expect("Sending failed.");
-                }
-            }
-            self.key_order.swap(fst_pos, snd_pos);
-        }
+    pub fn swap_tabs<Q>(&mut self, fst: &Q, snd: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.swap(fst, snd);
+        self.invalidated = true;
     }
 
     /// Removes a tab with the given id from the `TabView`.
     /// If the removed tab is active at the moment, the `TabView` will unfocus it and
     /// the focus needs to be set manually afterwards, or a new view has to be inserted.
-    pub fn remove_tab(&mut self, id: &str) -> Result<(), error::IdNotFound> {
-        if self.map.remove(id).is_some() {
-            if let Some(key) = &self.current_id {
-                if key == id {
-                    // Current id no longer valid
-                    self.current_id = None;
-                }
-            }
-            // remove_key experimental
-            self.key_order.retain(|k| k != id);
-            self.invalidated = true;
-            Ok(())
-        } else {
-            Err(error::IdNotFound { id: id.to_owned() })
+    ///
+    /// Children of the removed tab are moved to its parent, see `remove_tab_with` to close them instead.
+    pub fn remove_tab<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.remove_tab_with(id, ChildPolicy::Reparent)
+    }
+
+    /// Removes a tab with the given id from the `TabView`, the given policy decides whether
+    /// its children are closed as well or moved to the parent of the removed tab.
+    /// If the removed tab is active at the moment, the `TabView` will unfocus it and
+    /// the focus needs to be set manually afterwards, or a new view has to be inserted.
+    pub fn remove_tab_with<Q>(
+        &mut self,
+        id: &Q,
+        children: ChildPolicy,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        for key in self.model.remove(id, children)? {
+            self.map.remove::<K>(&key);
+            self.relayout_state.remove::<K>(&key);
+            self.data.remove::<K>(&key);
         }
+        self.invalidated = true;
+        Ok(())
     }
 
     /// Returns the current order of keys in a vector.
     /// When you're implementing your own tab bar, be aware that this is the current
     /// tab bar and is only a copy of the original order, modification will not be
     /// transferred and future updates in the original not displayed.
-    pub fn tab_order(&self) -> Vec<String> {
-        self.key_order.clone()
+    pub fn tab_order(&self) -> Vec<K> {
+        self.model.order().to_vec()
     }
 
-    // Returns the index of the key, length of the vector if the key is not included
-    // This can be done with out sorting
-    fn index_key(cur_key: &str, key_order: &[String]) -> usize {
-        for (idx, key) in key_order.iter().enumerate() {
-            if *key == *cur_key {
-                return idx;
-            }
+    /// Returns the current order of keys in a vector, leaving out all hidden tabs.
+    /// This is the order in which tabs are cycled with `next` and `prev`.
+    pub fn visible_tab_order(&self) -> Vec<K> {
+        self.model.visible_order()
+    }
+
+    /// Show or hide the tab with the given id.
+    /// Hidden tabs keep their view and state, but are skipped by `next` and `prev` and
+    /// are not shown in the tab bar of a `TabPanel`.
+    /// If the active tab gets hidden, the next visible tab in order will be set active.
+    /// Activating a hidden tab with `set_active_tab` shows it again.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_tab_visible<Q>(&mut self, id: &Q, visible: bool) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.set_visible(id, visible)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Show or hide the tab with the given id.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    ///
+    /// This is the consumable variant.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_visible<Q>(mut self, id: &Q, visible: bool) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_visible(id, visible) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
         }
-        key_order.len()
     }
 
-    /// Set the active tab to the next tab in order.
-    pub fn next(&mut self) {
-        if let Some(cur_key) = &self.current_id {
-            let idx = (Self::index_key(&cur_key, &self.key_order) + 1) % self.key_order.len();
+    /// Assign the tab with the given id to a named group, or remove it from its group with `None`.
+    /// Members of a group are always kept next to each other in the tab order, a tab joining a
+    /// group is moved behind the last tab already in it. Children belong to the group of their
+    /// top level tab, assigning a group to a child has no effect.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_tab_group<Q>(
+        &mut self,
+        id: &Q,
+        group: Option<&str>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.set_group(id, group)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Returns the name of the group the tab with the given id belongs to.
+    pub fn tab_group<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.group(id)
+    }
+
+    /// Make the tab with the given id a child of another tab, or a top level tab with `None`.
+    /// Children always directly follow their parent in the tab order, a tab getting a new
+    /// parent is moved, together with its own children, behind the last child of the parent.
+    /// Vertical tab bars draw children indented below their parent.
+    ///
+    /// If one of the ids is not known, an error is returned and no action is performed.
+    /// Making a tab a child of itself or of one of its own children is ignored.
+    pub fn set_tab_parent<Q>(
+        &mut self,
+        id: &Q,
+        parent: Option<&Q>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.set_parent(id, parent)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Returns the id of the parent of the given tab.
+    pub fn tab_parent<Q>(&self, id: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.parent(id)
+    }
+
+    /// Returns the ids of the direct children of the given tab in tab order.
+    pub fn tab_children<Q>(&self, id: &Q) -> Vec<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.children(id)
+    }
+
+    /// Mark an inactive tab as having new content, the mark is removed once the tab is activated.
+    /// Marking the active tab has no effect.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn notify_activity<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.model.notify_activity(id)?;
+        self.invalidated = true;
+        Ok(())
+    }
+
+    /// Returns the ids of all tabs with activity since they were last active, in tab order.
+    pub fn tabs_with_activity(&self) -> Vec<K> {
+        self.model.tabs_with_activity()
+    }
+
+    /// Returns whether the given tab had activity since it was last active.
+    pub fn has_activity<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.has_activity(id)
+    }
 
-            let key = &self.key_order[idx].clone();
-            self.set_active_tab(key)
-                .expect("Key content changed during operation, this should not happen");
+    /// Enable or disable the automatic detection of activity on inactive tabs, enabled by default.
+    /// An inactive tab is marked once its view requests a relayout, which most views do when
+    /// their content changes. `notify_activity` can be used for views which do not.
+    pub fn set_activity_polling(&mut self, enabled: bool) {
+        self.poll_activity = enabled;
+    }
+
+    /// Enable or disable the automatic detection of activity on inactive tabs.
+    ///
+    /// This is the consumable variant.
+    pub fn with_activity_polling(mut self, enabled: bool) -> Self {
+        self.set_activity_polling(enabled);
+        self
+    }
+
+    // Marks inactive tabs whose view started to require a relayout since the last check.
+    // Inactive views are laid out here so that they settle and later changes can be seen,
+    // otherwise a tab which has never been shown would never be marked.
+    fn poll_activity(&mut self, size: Vec2) {
+        let active = self.model.active().cloned();
+        for (key, view) in self.map.iter_mut() {
+            let mut needs_relayout = view.needs_relayout();
+            if active.as_ref() != Some(key) && needs_relayout {
+                let before = self.relayout_state.get(key).copied();
+                if before == Some(false) {
+                    // Keys are not required to implement `Debug`, so the error is dropped
+                    self.model
+                        .notify_activity::<K>(key)
+                        .map_err(drop)
+                        .expect("Key content changed during operation, this should not happen");
+                }
+                view.layout(size);
+                needs_relayout = view.needs_relayout();
+            }
+            self.relayout_state.insert(key.clone(), needs_relayout);
         }
     }
 
-    /// Set the active tab to the previous tab in order.
-    pub fn prev(&mut self) {
-        if let Some(cur_key) = self.current_id.as_ref().cloned() {
-            let idx_key = Self::index_key(&cur_key, &self.key_order);
-            let idx = (self.key_order.len() + idx_key - 1) % self.key_order.len();
+    /// Returns whether the tab with the given id is shown.
+    /// Unknown ids are reported as not visible.
+    pub fn is_tab_visible<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.model.is_visible(id)
+    }
 
-            let key = &self.key_order[idx].clone();
-            self.set_active_tab(key)
-                .expect("Key content changed during operation, this should not happen");
+    /// Apply many changes to the tabs at once, e.g. to replace all tabs after reconnecting.
+    ///
+    /// In contrast to single changes a batch always ends with an active tab, as long as there is a
+    /// visible one. If the active tab got removed, the tab active before the batch is activated
+    /// again, or the first visible tab if that one is gone as well.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabView;
+    /// let mut tabs = TabView::new().with_tab(TextView::new("Disconnected").with_name("Status"));
+    /// tabs.update(|tabs| {
+    ///     tabs.remove_tab("Status").expect("Id not found");
+    ///     for host in 0..20 {
+    ///         tabs.add_tab_with_id(format!("host-{}", host), TextView::new("Connected"));
+    ///     }
+    ///     tabs.set_active_tab("host-3").expect("Id not found");
+    /// });
+    /// assert_eq!(tabs.active_tab().map(String::as_str), Some("host-3"));
+    /// ```
+    pub fn update<R>(&mut self, changes: impl FnOnce(&mut Self) -> R) -> R {
+        let before = self.model.active().cloned();
+        let result = changes(self);
+        if self.model.active().is_none() {
+            let fallback = before
+                .filter(|key| self.model.is_visible(key))
+                .or_else(|| self.model.visible_order().into_iter().next());
+            if let Some(key) = fallback {
+                // Keys are not required to implement `Debug`, so the error is dropped
+                self.model
+                    .set_active::<K>(&key)
+                    .map_err(drop)
+                    .expect("Key content changed during operation, this should not happen");
+            }
         }
+        self.invalidated = true;
+        result
     }
 
-    /// Set the receiver for keys to be changed to
-    pub fn set_bar_rx(&mut self, rx: Receiver<String>) {
-        self.bar_rx = Some(rx);
+    /// Set the active tab to the next visible tab in order.
+    pub fn next(&mut self) {
+        self.model.next();
+        self.invalidated = true;
     }
 
-    /// Set the sender for the key switched to
-    pub fn set_active_key_tx(&mut self, tx: Sender<String>) {
-        self.active_key_tx = Some(tx);
+    /// Set the active tab to the previous visible tab in order.
+    pub fn prev(&mut self) {
+        self.model.prev();
+        self.invalidated = true;
     }
 }
 
-impl View for TabView {
+impl<K: Eq + Hash + Clone + Send + Sync + 'static> View for TabView<K> {
     fn draw(&self, printer: &Printer) {
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get(key) {
                 view.draw(printer);
             }
@@ -322,23 +990,18 @@ impl View for TabView {
 
     fn layout(&mut self, size: Vec2) {
         self.invalidated = false;
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get_mut(key) {
                 view.layout(size);
             }
         }
+        if self.poll_activity {
+            self.poll_activity(size);
+        }
     }
 
     fn required_size(&mut self, req: Vec2) -> Vec2 {
-        if let Some(rx) = &self.bar_rx {
-            if let Ok(evt) = rx.try_recv() {
-                match self.set_active_tab(&evt) {
-                    Ok(_) => {}
-                    Err(err) => debug!("could not accept tab bar event: {:?}", err),
-                }
-            }
-        }
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get_mut(key) {
                 view.required_size(req)
             } else {
@@ -350,7 +1013,7 @@ impl View for TabView {
     }
 
     fn on_event(&mut self, evt: Event) -> EventResult {
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get_mut(key) {
                 view.on_event(evt)
             } else {
@@ -362,7 +1025,7 @@ impl View for TabView {
     }
 
     fn take_focus(&mut self, src: Direction) -> Result<EventResult, CannotFocus> {
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get_mut(key) {
                 view.take_focus(src)
             } else {
@@ -380,7 +1043,7 @@ impl View for TabView {
     }
 
     fn focus_view(&mut self, slt: &Selector) -> Result<EventResult, ViewNotFound> {
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get_mut(key) {
                 view.focus_view(slt)
             } else {
@@ -393,7 +1056,7 @@ impl View for TabView {
 
     fn needs_relayout(&self) -> bool {
         self.invalidated || {
-            if let Some(key) = &self.current_id {
+            if let Some(key) = self.model.active() {
                 if let Some(view) = self.map.get(key) {
                     view.needs_relayout()
                 } else {
@@ -406,7 +1069,7 @@ impl View for TabView {
     }
 
     fn important_area(&self, size: Vec2) -> Rect {
-        if let Some(key) = &self.current_id {
+        if let Some(key) = self.model.active() {
             if let Some(view) = self.map.get(key) {
                 view.important_area(size)
             } else {
@@ -420,8 +1083,14 @@ impl View for TabView {
 
 #[cfg(test)]
 mod test {
-    use super::TabView;
-    use cursive::{traits::Nameable, views::DummyView};
+    use super::{ChildPolicy, Progress, Status, TabPanel, TabView, ViewAccessError};
+    use cursive::{
+        event::{Event, MouseButton, MouseEvent},
+        traits::Nameable,
+        view::{Finder, View},
+        views::{DummyView, NamedView, TextView},
+        Vec2,
+    };
 
     #[test]
     fn smoke() {
@@ -452,4 +1121,341 @@ mod test {
         assert_eq!(tabs.remove_tab("1"), Ok(()));
         assert!(tabs.active_tab().is_none());
     }
+
+    #[test]
+    fn unnamed() {
+        let mut tabs = TabView::default()
+            .with_tab_with_id(0, TextView::new("First"))
+            .with_tab_with_id_at(1, TextView::new("Second"), 0);
+        assert_eq!(tabs.tab_order(), vec![1, 0]);
+        assert_eq!(tabs.tab_title(&0), Some("0"));
+        assert!(tabs.find_name::<TextView>("0").is_none());
+        tabs.set_active_tab(&0).expect("Id not taken");
+        assert!(tabs.active_view().is_some());
+    }
+
+    #[test]
+    fn unnamed_with_title() {
+        // Keys without a textual representation
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        struct Key(u8);
+
+        let tabs = TabView::default()
+            .with_tab_with_title(Key(0), "First", TextView::new("First"))
+            .with_tab_with_title_at(Key(1), "Second", TextView::new("Second"), 0);
+        assert!(tabs.tab_order() == vec![Key(1), Key(0)]);
+        assert_eq!(tabs.tab_title(&Key(0)), Some("First"));
+        assert!(tabs.active_tab() == Some(&Key(1)));
+    }
+
+    #[test]
+    fn typed_access() {
+        let mut tabs = TabView::new()
+            .with_tab(TextView::new("Named").with_name("0"))
+            .with_tab_with_id("1".to_owned(), TextView::new("Unnamed"));
+        assert_eq!(
+            tabs.call_on_tab("0", |view: &mut TextView| view
+                .get_content()
+                .source()
+                .to_owned()),
+            Ok("Named".to_owned())
+        );
+        assert_eq!(
+            tabs.call_on_tab("1", |view: &mut TextView| view
+                .get_content()
+                .source()
+                .to_owned()),
+            Ok("Unnamed".to_owned())
+        );
+        assert_eq!(
+            tabs.call_on_tab("1", |_: &mut DummyView| ()),
+            Err(ViewAccessError::TypeMismatch("1".to_owned()))
+        );
+        assert_eq!(
+            tabs.call_on_tab("2", |_: &mut TextView| ()),
+            Err(ViewAccessError::IdNotFound("2".to_owned()))
+        );
+        assert!(tabs.get_tab::<TextView, _>("1").is_ok());
+        assert!(tabs.get_tab::<NamedView<TextView>, _>("0").is_ok());
+        assert_eq!(
+            tabs.get_tab::<TextView, _>("0").err(),
+            Some(ViewAccessError::NamedView("0".to_owned()))
+        );
+        assert_eq!(
+            tabs.get_tab::<DummyView, _>("1").err(),
+            Some(ViewAccessError::TypeMismatch("1".to_owned()))
+        );
+        assert_eq!(
+            tabs.get_tab::<TextView, _>("2").err(),
+            Some(ViewAccessError::IdNotFound("2".to_owned()))
+        );
+    }
+
+    #[test]
+    fn iteration() {
+        let mut tabs = TabView::new()
+            .with_tab(TextView::new("First").with_name("0"))
+            .with_tab_with_id("1".to_owned(), TextView::new("Second"))
+            .with_tab_at(DummyView {}.with_name("2"), 0);
+        tabs.set_tab_title("1", "One").expect("Id not taken");
+        let tabs_in_order = tabs
+            .iter()
+            .map(|(id, title, _)| (id.as_str(), title))
+            .collect::<Vec<_>>();
+        assert_eq!(tabs_in_order, vec![("2", "2"), ("0", "0"), ("1", "One")]);
+        for (_, _, view) in tabs.iter_mut() {
+            view.layout(Vec2::new(10, 1));
+        }
+        assert_eq!(tabs.len(), 3);
+        assert!(!tabs.is_empty());
+        assert!(tabs.contains("1"));
+        assert_eq!(tabs.index_of("0"), Some(1));
+        assert_eq!(tabs.id_at(2).map(String::as_str), Some("1"));
+        assert_eq!(tabs.id_at(3), None);
+    }
+
+    #[test]
+    fn data() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("0"));
+        tabs.add_tab(DummyView {}.with_name("1"));
+        tabs.add_tab(DummyView {}.with_name("2"));
+        tabs.set_tab_data("0", 42usize).expect("Id not taken");
+        tabs.set_tab_data("1", "db-1".to_owned())
+            .expect("Id not taken");
+        assert!(tabs.set_tab_data("3", 0usize).is_err());
+        tabs.swap_tabs("0", "2");
+        tabs.set_tab_parent("0", Some("1")).expect("Id not taken");
+        assert_eq!(tabs.tab_data::<usize, _>("0"), Some(&42));
+        assert_eq!(tabs.tab_data::<String, _>("0"), None);
+        *tabs.tab_data_mut::<usize, _>("0").expect("Data attached") += 1;
+        assert_eq!(tabs.take_tab_data::<String, _>("0"), None);
+        assert_eq!(tabs.take_tab_data::<usize, _>("0"), Some(43));
+        assert_eq!(tabs.tab_data::<usize, _>("0"), None);
+        tabs.remove_tab("1").expect("Id not taken");
+        tabs.add_tab(DummyView {}.with_name("1"));
+        assert_eq!(tabs.tab_data::<String, _>("1"), None);
+    }
+
+    #[test]
+    fn batch() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("0"));
+        tabs.add_tab(DummyView {}.with_name("1"));
+        tabs.update(|tabs| {
+            tabs.remove_tab("1").expect("Id not taken");
+            tabs.add_tab(DummyView {}.with_name("2"));
+            tabs.remove_tab("2").expect("Id not taken");
+        });
+        assert_eq!(tabs.active_tab().map(String::as_str), Some("0"));
+        tabs.add_tab(DummyView {}.with_name("3"));
+        tabs.update(|tabs| {
+            tabs.set_tab_visible("0", false).expect("Id not taken");
+            tabs.remove_tab("3").expect("Id not taken");
+            tabs.add_tab_at(DummyView {}.with_name("4"), 0);
+            tabs.add_tab(DummyView {}.with_name("5"));
+            tabs.remove_tab("5").expect("Id not taken");
+        });
+        assert_eq!(tabs.active_tab().map(String::as_str), Some("4"));
+        let removed = tabs.update(|tabs| tabs.remove_tab("4").is_ok());
+        assert!(removed);
+        assert_eq!(tabs.active_tab(), None);
+    }
+
+    #[test]
+    fn replace() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(TextView::new("First").with_name("0"));
+        tabs.add_tab(TextView::new("Second").with_name("1"));
+        tabs.add_tab(TextView::new("Third").with_name("2"));
+        tabs.set_active_tab("1").expect("Id not taken");
+        tabs.set_tab_title("1", "One").expect("Id not taken");
+        let old = tabs
+            .replace_tab("1", TextView::new("Replaced"))
+            .expect("Id not taken");
+        assert!(old.downcast_ref::<NamedView<TextView>>().is_some());
+        assert_eq!(tabs.tab_order(), vec!["0", "1", "2"]);
+        assert_eq!(tabs.active_tab().map(String::as_str), Some("1"));
+        assert_eq!(tabs.tab_title("1"), Some("One"));
+        assert!(tabs.get_tab::<TextView, _>("1").is_ok());
+        tabs.layout(Vec2::new(10, 1));
+        tabs.set_active_tab("0").expect("Id not taken");
+        tabs.layout(Vec2::new(10, 1));
+        tabs.replace_tab("2", TextView::new("Quiet"))
+            .expect("Id not taken");
+        tabs.layout(Vec2::new(10, 1));
+        assert!(tabs.tabs_with_activity().is_empty());
+        assert!(tabs.replace_tab("3", DummyView {}).is_err());
+    }
+
+    #[test]
+    fn sorting() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("c"));
+        tabs.add_tab(DummyView {}.with_name("a"));
+        tabs.add_tab(DummyView {}.with_name("b"));
+        tabs.set_active_tab("c").expect("Id not taken");
+        tabs.sort_tabs_by_key(|(id, _)| id.clone());
+        assert_eq!(tabs.tab_order(), vec!["a", "b", "c"]);
+        assert_eq!(tabs.active_tab().map(String::as_str), Some("c"));
+        tabs.set_keep_sorted_by(|(fst, _), (snd, _)| snd.cmp(fst));
+        assert_eq!(tabs.tab_order(), vec!["c", "b", "a"]);
+        tabs.add_tab(DummyView {}.with_name("bb"));
+        tabs.add_tab_at(DummyView {}.with_name("d"), 2);
+        tabs.add_tab(DummyView {}.with_name("0"));
+        assert_eq!(tabs.tab_order(), vec!["d", "c", "bb", "b", "a", "0"]);
+        tabs.clear_keep_sorted();
+        tabs.add_tab(DummyView {}.with_name("e"));
+        assert_eq!(tabs.id_at(6).map(String::as_str), Some("e"));
+    }
+
+    #[test]
+    fn hidden() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("0"));
+        tabs.add_tab(DummyView {}.with_name("1"));
+        tabs.add_tab(DummyView {}.with_name("2"));
+        tabs.set_tab_visible("1", false).expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["0", "1", "2"]);
+        assert_eq!(tabs.visible_tab_order(), vec!["0", "2"]);
+        tabs.next();
+        assert_eq!(tabs.active_tab().expect("Id not correct"), "0");
+        tabs.next();
+        assert_eq!(tabs.active_tab().expect("Id not correct"), "2");
+        tabs.set_tab_visible("2", false).expect("Id not taken");
+        assert_eq!(tabs.active_tab().expect("Id not correct"), "0");
+        tabs.set_tab_visible("1", true).expect("Id not taken");
+        tabs.prev();
+        assert_eq!(tabs.active_tab().expect("Id not correct"), "1");
+        assert!(tabs.set_tab_visible("3", false).is_err());
+        // Activating a hidden tab shows it again
+        tabs.set_active_tab("2").expect("Id not taken");
+        assert!(tabs.is_tab_visible("2"));
+        assert_eq!(tabs.visible_tab_order(), vec!["0", "1", "2"]);
+    }
+
+    #[test]
+    fn groups() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("0"));
+        tabs.add_tab(DummyView {}.with_name("1"));
+        tabs.add_tab(DummyView {}.with_name("2"));
+        tabs.add_tab(DummyView {}.with_name("3"));
+        tabs.set_tab_group("0", Some("Logs")).expect("Id not taken");
+        tabs.set_tab_group("2", Some("Logs")).expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["0", "2", "1", "3"]);
+        tabs.set_tab_group("3", Some("Logs")).expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["0", "2", "3", "1"]);
+        tabs.set_tab_group("2", None).expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["0", "3", "2", "1"]);
+        tabs.swap_tabs("0", "1");
+        assert_eq!(tabs.tab_order(), vec!["1", "3", "0", "2"]);
+        assert_eq!(tabs.tab_group("3"), Some("Logs"));
+        assert_eq!(tabs.tab_group("2"), None);
+    }
+
+    #[test]
+    fn tree() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(DummyView {}.with_name("0"));
+        tabs.add_tab(DummyView {}.with_name("1"));
+        tabs.add_tab(DummyView {}.with_name("2"));
+        tabs.add_tab(DummyView {}.with_name("3"));
+        tabs.set_tab_parent("3", Some("0")).expect("Id not taken");
+        tabs.set_tab_parent("1", Some("3")).expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["0", "3", "1", "2"]);
+        tabs.set_tab_parent("0", Some("1")).expect("Id not taken");
+        assert_eq!(tabs.tab_parent("0"), None);
+        tabs.swap_tabs("0", "2");
+        assert_eq!(tabs.tab_order(), vec!["2", "0", "3", "1"]);
+        tabs.remove_tab("3").expect("Id not taken");
+        assert_eq!(tabs.tab_children("0"), vec!["1"]);
+        tabs.remove_tab_with("0", ChildPolicy::Close)
+            .expect("Id not taken");
+        assert_eq!(tabs.tab_order(), vec!["2"]);
+    }
+
+    #[test]
+    fn activity() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(TextView::new("First").with_name("0"));
+        tabs.add_tab(TextView::new("Second").with_name("1"));
+        tabs.add_tab(TextView::new("Third").with_name("2"));
+        tabs.set_active_tab("0").expect("Id not taken");
+        tabs.layout(Vec2::new(10, 1));
+        tabs.notify_activity("0").expect("Id not taken");
+        tabs.notify_activity("2").expect("Id not taken");
+        assert_eq!(tabs.tabs_with_activity(), vec!["2"]);
+        tabs.set_active_tab("1").expect("Id not taken");
+        tabs.layout(Vec2::new(10, 1));
+        tabs.call_on_name("0", |view: &mut TextView| view.set_content("Changed"));
+        tabs.layout(Vec2::new(10, 1));
+        assert_eq!(tabs.tabs_with_activity(), vec!["0", "2"]);
+        tabs.next();
+        assert_eq!(tabs.tabs_with_activity(), vec!["0"]);
+    }
+
+    #[test]
+    fn activity_never_active() {
+        let mut tabs = TabView::new();
+        tabs.add_tab(TextView::new("First").with_name("0"));
+        tabs.add_tab(TextView::new("Second").with_name("1"));
+        tabs.set_active_tab("1").expect("Id not taken");
+        tabs.layout(Vec2::new(10, 1));
+        assert!(tabs.tabs_with_activity().is_empty());
+        tabs.call_on_name("0", |view: &mut TextView| view.set_content("Changed"));
+        tabs.layout(Vec2::new(10, 1));
+        assert_eq!(tabs.tabs_with_activity(), vec!["0"]);
+    }
+
+    #[test]
+    fn click_before_layout() {
+        let mut panel = TabPanel::new();
+        for idx in 0..4 {
+            panel.add_tab(TextView::new("Content").with_name(format!("T{}", idx)));
+        }
+        panel.required_size(Vec2::new(80, 24));
+        panel.layout(Vec2::new(80, 24));
+        panel.remove_tab("T3").expect("Id not taken");
+        panel.remove_tab("T2").expect("Id not taken");
+        // The bar has not been laid out again since the tabs were removed
+        panel.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(11, 0),
+            event: MouseEvent::Release(MouseButton::Left),
+        });
+        assert_eq!(panel.tab_order(), vec!["T0", "T1"]);
+    }
+
+    #[test]
+    fn replaced_tab_state() {
+        let mut panel = TabPanel::new()
+            .with_tab(TextView::new("Old").with_name("Logs"))
+            .with_tab(TextView::new("Other").with_name("Other"));
+        for id in ["Logs", "Other"] {
+            panel.set_tab_badge(id, Some("9")).expect("Id not taken");
+            panel
+                .set_tab_status(id, Status::Error)
+                .expect("Id not taken");
+            panel
+                .set_tab_progress(id, Progress::Spinner)
+                .expect("Id not taken");
+        }
+        panel.update(|tabs| {
+            tabs.remove_tab("Logs").expect("Id not taken");
+            tabs.add_tab(TextView::new("New").with_name("Logs"));
+        });
+        assert_eq!(panel.tab_badge("Logs"), None);
+        assert_eq!(panel.tab_status("Logs"), Status::Normal);
+        assert_eq!(panel.tab_progress("Logs"), Progress::None);
+        panel
+            .set_tab_badge("Logs", Some("1"))
+            .expect("Id not taken");
+        panel.add_tab(TextView::new("Newer").with_name("Logs"));
+        assert_eq!(panel.tab_badge("Logs"), None);
+        // Other tabs keep their state
+        assert_eq!(panel.tab_badge("Other"), Some("9"));
+        assert_eq!(panel.tab_status("Other"), Status::Error);
+        assert_eq!(panel.tab_progress("Other"), Progress::Spinner);
+    }
 }
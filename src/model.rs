@@ -0,0 +1,710 @@
+use log::debug;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::error;
+use crate::ChildPolicy;
+
+/// Order and state of a set of tabs, without any views or drawing attached.
+///
+/// Every `TabView` keeps its tabs in a model, which `TabView` and the bar of a `TabPanel` merely
+/// render. It is available through `model` on both, e.g. to show the position of the active tab
+/// in a status line, and can also be used on its own to drive custom widgets.
+///
+/// Tabs are identified by a key of type `K`, which defaults to `String`, and carry a title which
+/// is shown to the user independently from the key.
+///
+/// # Example
+/// ```
+/// # use cursive_tabs::TabModel;
+/// let mut model = TabModel::default();
+/// model.add(1, "Logs");
+/// model.add(2, "Metrics");
+/// model.add(3, "Alerts");
+/// model.prev();
+///
+/// let position = model.active().and_then(|id| model.index_of(id)).map_or(0, |idx| idx + 1);
+/// assert_eq!(format!("{}/{}", position, model.len()), "2/3");
+/// assert_eq!(model.title(&2), Some("Metrics"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TabModel<K = String> {
+    order: Vec<K>,
+    active: Option<K>,
+    titles: HashMap<K, String>,
+    hidden: HashSet<K>,
+    groups: HashMap<K, String>,
+    parents: HashMap<K, K>,
+    activity: HashSet<K>,
+    // Revision at which every tab was added, tells a tab apart from a former one with the same id
+    generations: HashMap<K, u64>,
+    // Incremented on every change, to let the bar skip syncing an unchanged model
+    revision: u64,
+}
+
+impl<K> Default for TabModel<K> {
+    fn default() -> Self {
+        Self {
+            order: Vec::new(),
+            active: None,
+            titles: HashMap::new(),
+            hidden: HashSet::new(),
+            groups: HashMap::new(),
+            parents: HashMap::new(),
+            activity: HashSet::new(),
+            generations: HashMap::new(),
+            revision: 0,
+        }
+    }
+}
+
+impl TabModel {
+    /// Returns a new model without any tabs, keyed by strings.
+    /// Use `default` for models with other key types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K: Eq + Hash + Clone> TabModel<K> {
+    /// Returns a number which changes with every modification of the model, to cheaply find out
+    /// whether anything has to be redrawn.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns a number which changes whenever a tab with the given id is added, even if it
+    /// replaces a tab with the same id. Widgets keeping their own state per tab can use it to
+    /// reset that state for a new tab.
+    pub fn generation<Q>(&self, id: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.generations.get(id).copied()
+    }
+
+    /// Returns the number of tabs, including hidden ones.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns whether there are no tabs at all.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Returns whether a tab with the given id exists.
+    pub fn contains<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.titles.contains_key(id)
+    }
+
+    /// Returns the position of the given tab in the tab order.
+    pub fn index_of<Q>(&self, id: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.order.iter().position(|key| key.borrow() == id)
+    }
+
+    fn check<Q>(&self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if self.contains(id) {
+            Ok(())
+        } else {
+            Err(error::IdNotFound { id: id.to_owned() })
+        }
+    }
+
+    /// Returns the id of the active tab.
+    pub fn active(&self) -> Option<&K> {
+        self.active.as_ref()
+    }
+
+    fn is_active<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.active.as_ref().map(|key| key.borrow()) == Some(id)
+    }
+
+    /// Set the active tab, which also clears its activity mark. A hidden tab is shown again.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_active<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        self.hidden.remove(id);
+        self.activate(id.to_owned());
+        Ok(())
+    }
+
+    fn activate(&mut self, id: K) {
+        self.activity.remove(&id);
+        self.active = Some(id);
+        self.revision += 1;
+    }
+
+    /// Returns the title of the given tab.
+    pub fn title<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.titles.get(id).map(|title| title.as_str())
+    }
+
+    /// Set the title of a tab, which is shown instead of its id.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_title<Q>(
+        &mut self,
+        id: &Q,
+        title: impl Into<String>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        self.titles.insert(id.to_owned(), title.into());
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// Add a new tab with the given title at the end of the order and make it active.
+    /// A tab with the same id is replaced by the new one, which starts out visible, without a
+    /// group, parent or activity mark. Children of the replaced tab are kept.
+    pub fn add(&mut self, id: K, title: impl Into<String>) {
+        self.insert(id, title.into(), None);
+    }
+
+    /// Add a new tab with the given title at the given position and make it active.
+    /// If the position is greater than the number of tabs, the tab is appended.
+    pub fn add_at(&mut self, id: K, title: impl Into<String>, pos: usize) {
+        self.insert(id, title.into(), Some(pos));
+    }
+
+    fn insert(&mut self, id: K, title: String, pos: Option<usize>) {
+        // A tab replacing another one with the same id does not inherit any of its state
+        self.order.retain(|key| *key != id);
+        self.hidden.remove(&id);
+        self.groups.remove(&id);
+        self.parents.remove(&id);
+        self.activity.remove(&id);
+        match pos {
+            Some(pos) if pos < self.order.len() => self.order.insert(pos, id.clone()),
+            _ => self.order.push(id.clone()),
+        }
+        self.titles.insert(id.clone(), title);
+        self.generations.insert(id.clone(), self.revision);
+        self.active = Some(id);
+        self.normalize_order();
+        self.revision += 1;
+    }
+
+    /// Sort the tabs with a comparator of their ids and titles, tabs comparing equal keep their
+    /// order. Children stay behind their parent and groups stay together.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut((&K, &str), (&K, &str)) -> Ordering,
+    {
+        let titles = &self.titles;
+        let title = |key: &K| titles.get(key).map_or("", |title| title.as_str());
+        self.order
+            .sort_by(|fst, snd| compare((fst, title(fst)), (snd, title(snd))));
+        self.normalize_order();
+        self.revision += 1;
+    }
+
+    /// Swap the positions of two tabs.
+    /// If one of the given ids cannot be found, no operation is performed.
+    pub fn swap<Q>(&mut self, fst: &Q, snd: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let (Some(fst_pos), Some(snd_pos)) = (self.index_of(fst), self.index_of(snd)) {
+            self.order.swap(fst_pos, snd_pos);
+            self.normalize_order();
+            self.revision += 1;
+        }
+    }
+
+    /// Remove a tab, the policy decides whether its children are removed as well or moved to its
+    /// parent. Removing the active tab leaves no tab active.
+    ///
+    /// Returns the ids of all removed tabs, with the given one last.
+    pub fn remove<Q>(
+        &mut self,
+        id: &Q,
+        children: ChildPolicy,
+    ) -> Result<Vec<K>, error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        let mut removed = Vec::new();
+        match children {
+            ChildPolicy::Close => {
+                for child in self.children(id) {
+                    removed.extend(self.remove::<K>(&child, ChildPolicy::Close)?);
+                }
+            }
+            ChildPolicy::Reparent => {
+                let parent = self.parents.get(id).cloned();
+                let group = self.groups.get(id).cloned();
+                for child in self.children(id) {
+                    match &parent {
+                        Some(parent) => {
+                            self.parents.insert(child, parent.clone());
+                        }
+                        None => {
+                            // Children becoming top level tabs stay in the group of the removed tab
+                            self.parents.remove::<K>(&child);
+                            if let Some(group) = &group {
+                                self.groups.insert(child, group.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if self.is_active(id) {
+            // Current id no longer valid
+            self.active = None;
+        }
+        self.order.retain(|key| key.borrow() != id);
+        self.titles.remove(id);
+        self.hidden.remove(id);
+        self.groups.remove(id);
+        self.parents.remove(id);
+        self.activity.remove(id);
+        self.generations.remove(id);
+        self.revision += 1;
+        removed.push(id.to_owned());
+        Ok(removed)
+    }
+
+    /// Returns the ids of all tabs in order.
+    pub fn order(&self) -> &[K] {
+        &self.order
+    }
+
+    /// Returns the ids of all tabs in order, leaving out hidden ones.
+    pub fn visible_order(&self) -> Vec<K> {
+        self.order
+            .iter()
+            .filter(|key| !self.hidden.contains(*key))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns whether the given tab exists and is not hidden.
+    pub fn is_visible<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.contains(id) && !self.hidden.contains(id)
+    }
+
+    /// Show or hide a tab, hiding the active tab activates the next visible one.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_visible<Q>(&mut self, id: &Q, visible: bool) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        if visible {
+            self.hidden.remove(id);
+        } else if self.hidden.insert(id.to_owned()) && self.is_active(id) {
+            match self.neighbour(id, true) {
+                Some(key) => self.activate(key),
+                None => self.active = None,
+            }
+        }
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// Returns the group of the given tab, children belong to the group of their top level tab.
+    pub fn group<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.parents.get(id) {
+            Some(parent) => self
+                .groups
+                .get::<K>(self.ancestors(parent).last().unwrap_or(parent)),
+            None => self.groups.get(id),
+        }
+        .map(|group| group.as_str())
+    }
+
+    /// Assign a top level tab to a group, or remove it from its group with `None`.
+    /// Members of a group are kept next to each other together with their children, a joining
+    /// tab moves behind the last one. Children always belong to the group of their top level tab,
+    /// so assigning a group to a child is ignored.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_group<Q>(&mut self, id: &Q, group: Option<&str>) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        if self.parents.contains_key(id) {
+            debug!("ignoring group of a child tab, it belongs to the group of its top level tab");
+            return Ok(());
+        }
+        match group {
+            Some(group) if self.group(id) != Some(group) => {
+                let pos = self.index(id);
+                let key = self.order.remove(pos);
+                let target = self
+                    .order
+                    .iter()
+                    .rposition(|key| self.group::<K>(key) == Some(group))
+                    .map_or(pos, |last| last + 1);
+                self.order.insert(target, key);
+                self.groups.insert(id.to_owned(), group.to_owned());
+            }
+            Some(_) => {}
+            None => {
+                self.groups.remove(id);
+            }
+        }
+        self.normalize_order();
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// Returns the parent of the given tab.
+    pub fn parent<Q>(&self, id: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.parents.get(id)
+    }
+
+    /// Make a tab the child of another tab, or a top level tab with `None`.
+    /// Children directly follow their parent, a tab getting a new parent moves behind its last child.
+    /// If one of the ids is not known, an error is returned. Cycles are ignored.
+    pub fn set_parent<Q>(&mut self, id: &Q, parent: Option<&Q>) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        for key in std::iter::once(id).chain(parent) {
+            self.check(key)?;
+        }
+        match parent {
+            Some(parent) => {
+                let id = id.to_owned();
+                let parent = parent.to_owned();
+                if parent == id || self.ancestors(&parent).any(|key| *key == id) {
+                    debug!("ignoring parent of a tab, this would create a cycle");
+                    return Ok(());
+                }
+                // Children are sorted by their position, so this makes the tab the last child
+                self.order.retain(|key| *key != id);
+                self.order.push(id.clone());
+                self.groups.remove::<K>(&id);
+                self.parents.insert(id, parent);
+            }
+            None => {
+                self.parents.remove(id);
+            }
+        }
+        self.normalize_order();
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// Returns the direct children of the given tab in order.
+    pub fn children<Q>(&self, id: &Q) -> Vec<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.order
+            .iter()
+            .filter(|key| self.parent::<K>(key).map(|parent| parent.borrow()) == Some(id))
+            .cloned()
+            .collect()
+    }
+
+    fn ancestors<'a>(&'a self, id: &'a K) -> impl Iterator<Item = &'a K> {
+        std::iter::successors(self.parent(id), move |key| self.parent(*key))
+    }
+
+    // Restores the invariants of the tab order, children follow their parent and the top level
+    // tabs of a group stay together at the position of their first member
+    fn normalize_order(&mut self) {
+        if self.parents.is_empty() && self.groups.is_empty() {
+            return;
+        }
+        let mut order = Vec::with_capacity(self.order.len());
+        let mut placed = HashSet::new();
+        let roots = || {
+            self.order
+                .iter()
+                .filter(|key| !self.parents.contains_key(*key))
+        };
+        for key in roots() {
+            match self.groups.get(key) {
+                Some(group) if placed.insert(group) => {
+                    for member in roots().filter(|member| self.groups.get(*member) == Some(group)) {
+                        self.push_subtree(member, &mut order);
+                    }
+                }
+                Some(_) => {}
+                None => self.push_subtree(key, &mut order),
+            }
+        }
+        self.order = order;
+    }
+
+    fn push_subtree(&self, id: &K, order: &mut Vec<K>) {
+        order.push(id.clone());
+        for child in self.children(id) {
+            self.push_subtree(&child, order);
+        }
+    }
+
+    /// Mark an inactive tab as having new content, marking the active tab has no effect.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn notify_activity<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.check(id)?;
+        if !self.is_active(id) && self.activity.insert(id.to_owned()) {
+            self.revision += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the ids of all tabs with activity since they were last active, in order.
+    pub fn tabs_with_activity(&self) -> Vec<K> {
+        self.order
+            .iter()
+            .filter(|key| self.activity.contains(*key))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns whether the given tab had activity since it was last active.
+    pub fn has_activity<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.activity.contains(id)
+    }
+
+    // Returns the index of the key, length of the order if the key is not included
+    fn index<Q>(&self, id: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index_of(id).unwrap_or(self.order.len())
+    }
+
+    // Returns the closest visible key before or after the given one, wrapping around at the ends
+    fn neighbour<Q>(&self, id: &Q, forward: bool) -> Option<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let len = self.order.len();
+        let idx_key = self.index(id);
+        (1..=len)
+            .map(|step| {
+                if forward {
+                    (idx_key + step) % len
+                } else {
+                    (idx_key + len - step) % len
+                }
+            })
+            .map(|idx| &self.order[idx])
+            .find(|key| !self.hidden.contains::<K>(key))
+            .cloned()
+    }
+
+    /// Activate the next visible tab, wrapping around at the end.
+    pub fn next(&mut self) {
+        self.step(true);
+    }
+
+    /// Activate the previous visible tab, wrapping around at the start.
+    pub fn prev(&mut self) {
+        self.step(false);
+    }
+
+    fn step(&mut self, forward: bool) {
+        if let Some(cur_key) = &self.active {
+            if let Some(key) = self.neighbour::<K>(cur_key, forward) {
+                self.activate(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TabModel;
+    use crate::ChildPolicy;
+
+    #[test]
+    fn cycle() {
+        let mut model = TabModel::new();
+        model.add("0".to_owned(), "Zero");
+        model.add("1".to_owned(), "One");
+        model.add_at("2".to_owned(), "Two", 0);
+        assert_eq!(model.order(), ["2", "0", "1"]);
+        assert_eq!(model.active().map(String::as_str), Some("2"));
+        model.prev();
+        assert_eq!(model.active().map(String::as_str), Some("1"));
+        model.set_visible("2", false).expect("Id not taken");
+        model.next();
+        assert_eq!(model.active().map(String::as_str), Some("0"));
+        assert_eq!(model.index_of("0"), Some(1));
+        assert!(model.set_active("3").is_err());
+    }
+
+    #[test]
+    fn remove_policies() {
+        let mut model = TabModel::default();
+        for id in 0..4 {
+            model.add(id, id.to_string());
+        }
+        model.set_parent(&1, Some(&0)).expect("Id not taken");
+        model.set_parent(&2, Some(&1)).expect("Id not taken");
+        assert_eq!(model.remove(&1, ChildPolicy::Reparent), Ok(vec![1]));
+        assert_eq!(model.parent(&2), Some(&0));
+        assert_eq!(model.remove(&0, ChildPolicy::Close), Ok(vec![2, 0]));
+        assert_eq!(model.order(), [3]);
+        assert_eq!(model.active(), Some(&3));
+        assert_eq!(model.title(&0), None);
+    }
+
+    #[test]
+    fn groups() {
+        let mut model = TabModel::new();
+        for id in ["g1", "p", "c", "g2"] {
+            model.add(id.to_owned(), id);
+        }
+        model.set_group("g1", Some("G")).expect("Id not taken");
+        model.set_parent("c", Some("p")).expect("Id not taken");
+        model.set_group("c", Some("G")).expect("Id not taken");
+        assert_eq!(model.order(), ["g1", "p", "c", "g2"]);
+        assert_eq!(model.group("c"), None);
+        model.set_group("g2", Some("G")).expect("Id not taken");
+        model.set_group("p", Some("G")).expect("Id not taken");
+        assert_eq!(model.order(), ["g1", "g2", "p", "c"]);
+        assert_eq!(model.group("c"), Some("G"));
+        model
+            .remove("p", ChildPolicy::Reparent)
+            .expect("Id not taken");
+        assert_eq!(model.group("c"), Some("G"));
+        model.add("x".to_owned(), "x");
+        model.set_parent("x", Some("g1")).expect("Id not taken");
+        assert_eq!(model.order(), ["g1", "x", "g2", "c"]);
+    }
+
+    #[test]
+    fn replace() {
+        let mut model = TabModel::default();
+        for id in 0..4 {
+            model.add(id, id.to_string());
+        }
+        model.set_parent(&1, Some(&0)).expect("Id not taken");
+        model.set_parent(&2, Some(&3)).expect("Id not taken");
+        model.set_group(&3, Some("group")).expect("Id not taken");
+        model.set_visible(&3, false).expect("Id not taken");
+        model.notify_activity(&3).expect("Id not taken");
+        let generation = model.generation(&0);
+        model.add(0, "Zero");
+        assert_ne!(model.generation(&0), generation);
+        assert_eq!(model.order(), [3, 2, 0, 1]);
+        model.add(3, "Three");
+        assert!(model.is_visible(&3));
+        assert_eq!(model.group(&3), None);
+        assert!(!model.has_activity(&3));
+        assert_eq!(model.order(), [0, 1, 3, 2]);
+        model.add(2, "Two");
+        assert_eq!(model.parent(&2), None);
+        assert_eq!(model.order(), [0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn revision() {
+        let mut model = TabModel::new();
+        model.add("0".to_owned(), "0");
+        let revision = model.revision();
+        model.swap("0", "1");
+        assert_eq!(model.revision(), revision);
+        model.add("1".to_owned(), "1");
+        model.notify_activity("0").expect("Id not taken");
+        assert!(model.has_activity("0"));
+        assert!(model.revision() > revision);
+    }
+
+    #[test]
+    fn sort() {
+        let mut model = TabModel::default();
+        for (id, title) in [(3, "c"), (1, "a"), (4, "d"), (2, "b")] {
+            model.add(id, title);
+        }
+        model.set_parent(&4, Some(&3)).expect("Id not taken");
+        model.sort_by(|(_, fst), (_, snd)| snd.cmp(fst));
+        assert_eq!(model.order(), [3, 4, 2, 1]);
+        model.sort_by(|(fst, _), (snd, _)| fst.cmp(snd));
+        assert_eq!(model.order(), [1, 2, 3, 4]);
+        assert_eq!(model.active(), Some(&2));
+    }
+
+    #[test]
+    fn titles() {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        enum Pane {
+            Logs,
+            Metrics,
+        }
+
+        let mut model = TabModel::default();
+        model.add(Pane::Logs, "Logs");
+        model.add(Pane::Metrics, "Metrics");
+        model
+            .set_title(&Pane::Logs, "Logs (3)")
+            .expect("Id not taken");
+        assert_eq!(model.title(&Pane::Logs), Some("Logs (3)"));
+        model.add(Pane::Logs, "Logs");
+        assert_eq!(model.order(), [Pane::Metrics, Pane::Logs]);
+        assert_eq!(model.title(&Pane::Logs), Some("Logs"));
+        assert!(model.remove(&Pane::Logs, ChildPolicy::Close).is_ok());
+        assert_eq!(
+            model.set_title(&Pane::Logs, "Logs"),
+            Err(crate::error::IdNotFound { id: Pane::Logs })
+        );
+    }
+}
@@ -1,15 +1,21 @@
-use crossbeam::channel::{unbounded, Sender};
 use cursive::direction::{Absolute, Direction};
 use cursive::event::{AnyCb, Event, EventResult, Key};
+use cursive::theme::{Color, ColorStyle, Effect, EffectStatus, Effects, Style};
 use cursive::view::{CannotFocus, Selector, View, ViewNotFound};
 use cursive::views::NamedView;
 use cursive::{Printer, Vec2};
 use log::debug;
 use num::clamp;
+use std::any::Any;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::hash::Hash;
 
 use crate::error;
-use crate::Bar;
+use crate::ChildPolicy;
 use crate::TabBar;
+use crate::TabModel;
 use crate::TabView;
 
 #[derive(Clone, Copy, Debug)]
@@ -17,23 +23,250 @@ pub enum Align {
     Start,
     Center,
     End,
+    /// Tabs grow to share the whole length of the bar evenly.
+    Stretch,
+    /// Tabs keep their size and the free space is distributed between them.
+    SpaceBetween,
 }
 
+/// Side of the `TabPanel` the bar is placed on.
+///
+/// Horizontal bars are drawn into the border line of the panel itself, with the tabs cut
+/// into it like in a notebook.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Placement {
+    /// Vertical bar left of the content.
     VerticalLeft,
+    /// Vertical bar right of the content.
     VerticalRight,
+    /// The bar takes the place of the top border.
     HorizontalTop,
+    /// The bar takes the place of the bottom border.
     HorizontalBottom,
 }
 
+/// Progress of a background job shown next to the title of a tab.
+///
+/// The spinner advances with every redraw of the bar, to keep it moving while nothing
+/// else happens enable regular refreshes of cursive with `set_fps` or `set_autorefresh`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Progress {
+    /// No indicator is shown.
+    #[default]
+    None,
+    /// An animated spinner for jobs of unknown length.
+    Spinner,
+    /// A percentage between 0 and 100.
+    Percent(u8),
+}
+
+/// Severity of a tab, drawn with a distinct color in the tab bar.
+///
+/// The variants are ordered by severity, a collapsed group or tree shows the most severe
+/// status of the tabs it hides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Status {
+    /// Drawn with the usual title colors.
+    #[default]
+    Normal,
+    /// Drawn in green.
+    Success,
+    /// Drawn in yellow.
+    Warning,
+    /// Drawn in red.
+    Error,
+}
+
+/// Set of characters used for the border of a `TabPanel` and the separators in its bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    /// Light lines, with heavy separators around the active tab.
+    #[default]
+    Light,
+    /// Heavy lines.
+    Heavy,
+    /// Double lines.
+    Double,
+    /// Light lines with rounded corners.
+    Rounded,
+    /// Plain ASCII characters, for terminals and fonts without box drawing characters.
+    Ascii,
+}
+
+/// Separators drawn between and around the tabs of the bar.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// Lines of the `BorderStyle` of the panel, joined with its border.
+    #[default]
+    Border,
+    /// Custom text, the second variant is used next to the active tab.
+    ///
+    /// Both should have the same width. Vertical bars fill the row between two tabs with it.
+    Text(String, String),
+    /// No separators at all, tabs are only set apart by their padding and the gap between them.
+    None,
+}
+
+// Characters of a border style, separators hold the normal variant first and the one used
+// next to the active tab second
+pub(crate) struct Glyphs {
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    // Between tabs of a horizontal and a vertical bar
+    pub separator: [&'static str; 2],
+    pub divider: [&'static str; 2],
+    // Where a horizontal bar starts and ends on the border
+    pub open: [&'static str; 2],
+    pub close: [&'static str; 2],
+    // Where a vertical bar starts and ends on the border
+    pub open_vertical: [&'static str; 2],
+    pub close_vertical: [&'static str; 2],
+    // Marks the end of a horizontal and a vertical bar with more tabs than fit into it
+    pub overflow: [&'static str; 2],
+}
+
+const LIGHT: Glyphs = Glyphs {
+    horizontal: "─",
+    vertical: "│",
+    top_left: "┌",
+    top_right: "┐",
+    bottom_left: "└",
+    bottom_right: "┘",
+    separator: ["│", "┃"],
+    divider: ["─", "━"],
+    open: ["┤", "┨"],
+    close: ["├", "┠"],
+    open_vertical: ["┴", "┷"],
+    close_vertical: ["┬", "┯"],
+    overflow: ["…", "⋮"],
+};
+
+const HEAVY: Glyphs = Glyphs {
+    horizontal: "━",
+    vertical: "┃",
+    top_left: "┏",
+    top_right: "┓",
+    bottom_left: "┗",
+    bottom_right: "┛",
+    separator: ["│", "┃"],
+    divider: ["─", "━"],
+    open: ["┥", "┫"],
+    close: ["┝", "┣"],
+    open_vertical: ["┸", "┻"],
+    close_vertical: ["┰", "┳"],
+    overflow: ["…", "⋮"],
+};
+
+const DOUBLE: Glyphs = Glyphs {
+    horizontal: "═",
+    vertical: "║",
+    top_left: "╔",
+    top_right: "╗",
+    bottom_left: "╚",
+    bottom_right: "╝",
+    separator: ["│", "║"],
+    divider: ["─", "═"],
+    open: ["╡", "╣"],
+    close: ["╞", "╠"],
+    open_vertical: ["╨", "╩"],
+    close_vertical: ["╥", "╦"],
+    overflow: ["…", "⋮"],
+};
+
+const ROUNDED: Glyphs = Glyphs {
+    top_left: "╭",
+    top_right: "╮",
+    bottom_left: "╰",
+    bottom_right: "╯",
+    ..LIGHT
+};
+
+const ASCII: Glyphs = Glyphs {
+    horizontal: "-",
+    vertical: "|",
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    separator: ["|", "|"],
+    divider: ["-", "="],
+    open: ["+", "+"],
+    close: ["+", "+"],
+    open_vertical: ["+", "+"],
+    close_vertical: ["+", "+"],
+    overflow: [">", "v"],
+};
+
+impl BorderStyle {
+    pub(crate) fn glyphs(self) -> &'static Glyphs {
+        match self {
+            BorderStyle::Light => &LIGHT,
+            BorderStyle::Heavy => &HEAVY,
+            BorderStyle::Double => &DOUBLE,
+            BorderStyle::Rounded => &ROUNDED,
+            BorderStyle::Ascii => &ASCII,
+        }
+    }
+}
+
+/// Styles used to draw the tab bar and the border of a `TabPanel`.
+///
+/// The default reproduces the look of the bar with bold titles in the title colors of the
+/// current theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TabStyle {
+    /// Title of the active tab.
+    pub active: Style,
+    /// Titles of all other tabs.
+    pub inactive: Style,
+    /// Title below the cursor while the bar has focus.
+    pub focused: Style,
+    /// Titles while the panel is disabled.
+    pub disabled: Style,
+    /// Applied on top of the other styles while the mouse is held down over a tab, terminals
+    /// only report the position of the mouse while a button is pressed.
+    pub hovered: Style,
+    /// Outer border of the panel and the line the tabs are placed on.
+    pub border: Style,
+    /// Separators between tabs and the connectors at both ends of the bar.
+    pub separator: Style,
+}
+
+// Bold variant of a style, regardless of the effects of the surrounding view
+fn bold(style: Style) -> Style {
+    let mut style = style;
+    style.effects[Effect::Bold] = EffectStatus::On;
+    style
+}
+
+impl Default for TabStyle {
+    fn default() -> Self {
+        Self {
+            active: bold(ColorStyle::title_primary().into()),
+            inactive: bold(ColorStyle::title_secondary().into()),
+            focused: bold(Style {
+                color: ColorStyle::highlight().invert(),
+                effects: Effects::only(Effect::Reverse),
+            }),
+            disabled: bold(ColorStyle::secondary().into()),
+            hovered: Style::none(),
+            border: Style::none(),
+            separator: Style::none(),
+        }
+    }
+}
+
 impl Align {
     pub fn get_offset(self, content: usize, container: usize) -> usize {
         if container < content {
             0
         } else {
             match self {
-                Align::Start => 0,
+                Align::Start | Align::Stretch | Align::SpaceBetween => 0,
                 Align::Center => (container - content) / 2,
                 Align::End => container - content,
             }
@@ -56,50 +289,87 @@ impl Align {
 ///       .with_bar_alignment(Align::Center);
 /// ```
 ///
+/// With a horizontal placement the bar is the title line of the panel, so instead of wrapping the
+/// panel in a `Dialog` use the panel itself as the frame. The bar cannot reach into the frame of a
+/// surrounding view, inside one use `with_borderless` to avoid drawing two frames.
+///
+/// If there are more tabs than fit into the bar, the last cell of the bar shows a marker in the
+/// style of the separators.
+///
 /// A TabView is also usable separately, so if you prefer the tabs without the TabBar and Panel around have a look at `TabView`.
-pub struct TabPanel {
-    bar: TabBar,
+pub struct TabPanel<K = String> {
+    bar: TabBar<K>,
     bar_size: Vec2,
     tab_size: Vec2,
-    tx: Sender<String>,
-    tabs: TabView,
+    tabs: TabView<K>,
     bar_focused: bool,
     bar_align: Align,
     bar_placement: Placement,
+    borderless: bool,
 }
 
-impl Default for TabPanel {
+impl<K: Eq + Hash + Clone> Default for TabPanel<K> {
     fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl TabPanel {
-    /// Returns a new instance of a TabPanel.
-    /// Alignment is set by default to left, to change this use `set_bar_alignment` to change to any other `HAlign` provided by `cursive`.
-    pub fn new() -> Self {
-        let mut tabs = TabView::new();
-        let (tx, rx) = unbounded();
-        let (active_tx, active_rx) = unbounded();
-        tabs.set_bar_rx(rx);
-        tabs.set_active_key_tx(active_tx);
         Self {
-            bar: TabBar::new(active_rx)
+            bar: TabBar::new()
                 .with_placement(Placement::HorizontalTop)
                 .with_alignment(Align::Start),
             bar_size: Vec2::new(1, 1),
             tab_size: Vec2::new(1, 1),
-            tabs,
-            tx,
+            tabs: TabView::default(),
             bar_focused: true,
             bar_align: Align::Start,
             bar_placement: Placement::HorizontalTop,
+            borderless: false,
         }
     }
+}
+
+impl TabPanel {
+    /// Returns a new instance of a TabPanel.
+    /// Alignment is set by default to left, to change this use `set_bar_alignment` to change to any other `HAlign` provided by `cursive`.
+    ///
+    /// Tabs of this panel are identified by the names of their views, use `default` for a panel
+    /// with another key type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Non-consuming variant to add new tabs to the `TabView`.
+    /// Note: Calls `add_tab` on the enclosed `TabView`.
+    pub fn add_tab<T: View>(&mut self, view: NamedView<T>) {
+        self.tabs.add_tab(view);
+        self.sync_bar();
+    }
+
+    /// Consuming & Chainable variant to add a new tab.
+    /// Note: Calls `add_tab` on the enclosed `TabView`.
+    pub fn with_tab<T: View>(mut self, view: NamedView<T>) -> Self {
+        self.add_tab(view);
+        self
+    }
+
+    /// Non-consuming variant to add new tabs to the `TabView` at a certain position.
+    /// It is fail-safe, if the postion is greater than the amount of tabs, it is appended to the end.
+    /// Note: Calls `add_tab_at` on the enclosed `TabView`.
+    pub fn add_tab_at<T: View>(&mut self, view: NamedView<T>, pos: usize) {
+        self.tabs.add_tab_at(view, pos);
+        self.sync_bar();
+    }
 
+    /// Consuming & Chainable variant to add a new tab at a certain position.
+    /// It is fail-safe, if the postion is greater than the amount of tabs, it is appended to the end.
+    /// Note: Calls `add_tab_at` on the enclosed `TabView`.
+    pub fn with_tab_at<T: View>(mut self, view: NamedView<T>, pos: usize) -> Self {
+        self.add_tab_at(view, pos);
+        self
+    }
+}
+
+impl<K: Eq + Hash + Clone> TabPanel<K> {
     /// Returns the current active tab of the `TabView`.
     /// Note: Calls `active_tab` on the enclosed `TabView`.
-    pub fn active_tab(&self) -> Option<&str> {
+    pub fn active_tab(&self) -> Option<&K> {
         self.tabs.active_tab()
     }
 
@@ -121,79 +391,397 @@ impl TabPanel {
         self.tabs.views_mut()
     }
 
-    /// Non-consuming variant to set the active tab in the `TabView`.
+    /// Returns an iterator over the id, the title and the view of every tab, in tab order.
+    /// Note: Calls `iter` on the enclosed `TabView`.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &str, &dyn View)> {
+        self.tabs.iter()
+    }
+
+    /// Returns an iterator over the id, the title and the mutable view of every tab, in tab order.
+    /// Note: Calls `iter_mut` on the enclosed `TabView`.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &str, &mut dyn View)> {
+        self.tabs.iter_mut()
+    }
+
+    /// Returns the number of tabs, including hidden ones.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns whether there are no tabs at all.
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Returns whether a tab with the given id exists.
+    pub fn contains<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.contains(id)
+    }
+
+    /// Returns the position of the given tab in the tab order.
+    pub fn index_of<Q>(&self, id: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.index_of(id)
+    }
+
+    /// Returns the id of the tab at the given position in the tab order.
+    pub fn id_at(&self, idx: usize) -> Option<&K> {
+        self.tabs.id_at(idx)
+    }
+
+    /// Calls the callback with the view of the given tab, downcast to its concrete type.
+    /// Note: Calls `call_on_tab` on the enclosed `TabView`.
+    pub fn call_on_tab<V, R, Q>(
+        &mut self,
+        id: &Q,
+        cb: impl FnOnce(&mut V) -> R,
+    ) -> Result<R, error::ViewAccessError<K>>
+    where
+        V: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.call_on_tab(id, cb)
+    }
+
+    /// Returns the view of the given tab, downcast to its concrete type.
+    /// Note: Calls `get_tab` on the enclosed `TabView`.
+    pub fn get_tab<V, Q>(&self, id: &Q) -> Result<&V, error::ViewAccessError<K>>
+    where
+        V: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.get_tab(id)
+    }
+
+    /// Non-consuming variant to set the active tab in the `TabView`, a hidden tab is shown again.
     /// Note: Calls `set_active_tab` on the enclosed `TabView`.
-    pub fn set_active_tab(&mut self, id: &str) -> Result<(), error::IdNotFound> {
-        self.tabs.set_active_tab(id)
+    pub fn set_active_tab<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_active_tab(id)?;
+        self.sync_bar();
+        Ok(())
     }
 
     /// Consuming & Chainable variant to set the active tab in the `TabView`.
     ///  Note: Calls `set_active_tab` on the enclosed `TabView`.
     ///
-    pub fn with_active_tab(mut self, id: &str) -> Result<Self, Self> {
-        match self.tabs.set_active_tab(id) {
+    #[allow(clippy::result_large_err)]
+    pub fn with_active_tab<Q>(mut self, id: &Q) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_active_tab(id) {
             Ok(_) => Ok(self),
             Err(_) => Err(self),
         }
     }
 
-    /// Non-consuming variant to add new tabs to the `TabView`.
-    /// Note: Calls `add_tab` on the enclosed `TabView`.
-    pub fn add_tab<T: View>(&mut self, view: NamedView<T>) {
-        let id = view.name();
-        self.bar.add_button(self.tx.clone(), id);
-        self.tabs.add_tab(view);
+    /// Non-consuming variant to add a new tab with the given id, the name of the view is used as its title.
+    /// Note: Calls `add_keyed_tab` on the enclosed `TabView`.
+    pub fn add_keyed_tab<T: View>(&mut self, id: K, view: NamedView<T>) {
+        self.tabs.add_keyed_tab(id, view);
+        self.sync_bar();
     }
 
-    /// Consuming & Chainable variant to add a new tab.
-    /// Note: Calls `add_tab` on the enclosed `TabView`.
-    pub fn with_tab<T: View>(mut self, view: NamedView<T>) -> Self {
-        let id = view.name();
-        self.bar.add_button(self.tx.clone(), id);
-        self.tabs.add_tab(view);
+    /// Consuming & Chainable variant to add a new tab with the given id.
+    /// Note: Calls `add_keyed_tab` on the enclosed `TabView`.
+    pub fn with_keyed_tab<T: View>(mut self, id: K, view: NamedView<T>) -> Self {
+        self.add_keyed_tab(id, view);
+        self
+    }
+
+    /// Replace the view of a tab, keeping its position, title and active state in the bar.
+    /// Returns the view which has been replaced.
+    /// Note: Calls `replace_tab` on the enclosed `TabView`.
+    pub fn replace_tab<T, Q>(
+        &mut self,
+        id: &Q,
+        view: T,
+    ) -> Result<Box<dyn View>, error::IdNotFound<K>>
+    where
+        T: View,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.replace_tab(id, view)
+    }
+
+    /// Sort the tabs with a comparator of their ids and titles, the active tab stays active.
+    /// Note: Calls `sort_tabs_by` on the enclosed `TabView`.
+    pub fn sort_tabs_by<F>(&mut self, compare: F)
+    where
+        F: FnMut((&K, &str), (&K, &str)) -> Ordering,
+    {
+        self.tabs.sort_tabs_by(compare);
+        self.sync_bar();
+    }
+
+    /// Sort the tabs by a key extracted from their ids and titles.
+    /// Note: Calls `sort_tabs_by_key` on the enclosed `TabView`.
+    pub fn sort_tabs_by_key<T, F>(&mut self, key: F)
+    where
+        T: Ord,
+        F: FnMut((&K, &str)) -> T,
+    {
+        self.tabs.sort_tabs_by_key(key);
+        self.sync_bar();
+    }
+
+    /// Non-consuming variant to sort the tabs and keep them sorted, new tabs are inserted at their sorted position.
+    /// Note: Calls `set_keep_sorted_by` on the enclosed `TabView`.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabPanel;
+    /// let panel = TabPanel::new()
+    ///     .with_keep_sorted_by(|(_, fst), (_, snd)| fst.cmp(snd))
+    ///     .with_tab(TextView::new("Shopping list").with_name("Shop"))
+    ///     .with_tab(TextView::new("Recipes").with_name("Cook"));
+    /// assert_eq!(panel.tab_order(), vec!["Cook", "Shop"]);
+    /// ```
+    pub fn set_keep_sorted_by<F>(&mut self, compare: F)
+    where
+        F: Fn((&K, &str), (&K, &str)) -> Ordering + Send + Sync + 'static,
+    {
+        self.tabs.set_keep_sorted_by(compare);
+        self.sync_bar();
+    }
+
+    /// Consumable & Chainable variant to sort the tabs and keep them sorted.
+    pub fn with_keep_sorted_by<F>(mut self, compare: F) -> Self
+    where
+        F: Fn((&K, &str), (&K, &str)) -> Ordering + Send + Sync + 'static,
+    {
+        self.set_keep_sorted_by(compare);
         self
     }
 
+    /// Stop keeping the tabs sorted.
+    pub fn clear_keep_sorted(&mut self) {
+        self.tabs.clear_keep_sorted();
+    }
+
+    /// Returns whether new tabs are inserted at their sorted position.
+    pub fn is_kept_sorted(&self) -> bool {
+        self.tabs.is_kept_sorted()
+    }
+
     /// Swaps the given tab keys.
     /// If at least one of them cannot be found then no operation is performed
-    pub fn swap_tabs(&mut self, fst: &str, snd: &str) {
+    pub fn swap_tabs<Q>(&mut self, fst: &Q, snd: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.tabs.swap_tabs(fst, snd);
-        self.bar.swap_button(fst, snd);
+        self.sync_bar();
     }
 
-    /// Non-consuming variant to add new tabs to the `TabView` at a certain position.
+    /// Non-consuming variant to add a new tab with the given id at a certain position.
     /// It is fail-safe, if the postion is greater than the amount of tabs, it is appended to the end.
-    /// Note: Calls `add_tab_at` on the enclosed `TabView`.
-    pub fn add_tab_at<T: View>(&mut self, view: NamedView<T>, pos: usize) {
-        let id = view.name();
-        self.bar.add_button_at(self.tx.clone(), id, pos);
-        self.tabs.add_tab_at(view, pos);
+    /// Note: Calls `add_keyed_tab_at` on the enclosed `TabView`.
+    pub fn add_keyed_tab_at<T: View>(&mut self, id: K, view: NamedView<T>, pos: usize) {
+        self.tabs.add_keyed_tab_at(id, view, pos);
+        self.sync_bar();
     }
 
-    /// Consuming & Chainable variant to add a new tab at a certain position.
+    /// Consuming & Chainable variant to add a new tab with the given id at a certain position.
+    /// Note: Calls `add_keyed_tab_at` on the enclosed `TabView`.
+    pub fn with_keyed_tab_at<T: View>(mut self, id: K, view: NamedView<T>, pos: usize) -> Self {
+        self.add_keyed_tab_at(id, view, pos);
+        self
+    }
+
+    /// Non-consuming variant to add a new tab with the given id and title, the view does not need
+    /// to be named and the id needs no textual representation.
+    /// Note: Calls `add_tab_with_title` on the enclosed `TabView`.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::views::TextView;
+    /// # use cursive_tabs::TabPanel;
+    /// #[derive(Clone, PartialEq, Eq, Hash)]
+    /// enum Pane {
+    ///     Logs,
+    ///     Hosts,
+    /// }
+    ///
+    /// let panel = TabPanel::default()
+    ///     .with_tab_with_title(Pane::Logs, "Logs", TextView::new("No logs yet"))
+    ///     .with_tab_with_title(Pane::Hosts, "Hosts", TextView::new("No hosts yet"));
+    /// assert_eq!(panel.tab_title(&Pane::Hosts), Some("Hosts"));
+    /// ```
+    pub fn add_tab_with_title<T: View>(&mut self, id: K, title: impl Into<String>, view: T) {
+        self.tabs.add_tab_with_title(id, title, view);
+        self.sync_bar();
+    }
+
+    /// Consuming & Chainable variant to add a new tab with the given id and title.
+    /// Note: Calls `add_tab_with_title` on the enclosed `TabView`.
+    pub fn with_tab_with_title<T: View>(
+        mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+    ) -> Self {
+        self.add_tab_with_title(id, title, view);
+        self
+    }
+
+    /// Non-consuming variant to add a new tab with the given id and title at a certain position, the view does not need to be named.
     /// It is fail-safe, if the postion is greater than the amount of tabs, it is appended to the end.
-    /// Note: Calls `add_tab_at` on the enclosed `TabView`.
-    pub fn with_tab_at<T: View>(mut self, view: NamedView<T>, pos: usize) -> Self {
-        let id = view.name();
-        self.bar.add_button_at(self.tx.clone(), id, pos);
-        self.tabs.add_tab_at(view, pos);
+    /// Note: Calls `add_tab_with_title_at` on the enclosed `TabView`.
+    pub fn add_tab_with_title_at<T: View>(
+        &mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+        pos: usize,
+    ) {
+        self.tabs.add_tab_with_title_at(id, title, view, pos);
+        self.sync_bar();
+    }
+
+    /// Consuming & Chainable variant to add a new tab with the given id and title at a certain position.
+    /// Note: Calls `add_tab_with_title_at` on the enclosed `TabView`.
+    pub fn with_tab_with_title_at<T: View>(
+        mut self,
+        id: K,
+        title: impl Into<String>,
+        view: T,
+        pos: usize,
+    ) -> Self {
+        self.add_tab_with_title_at(id, title, view, pos);
+        self
+    }
+
+    /// Non-consuming variant to add a new tab with the given id, the view does not need to be named.
+    /// The id is shown as title in the bar until another one is set with `set_tab_title`.
+    /// Note: Calls `add_tab_with_id` on the enclosed `TabView`.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::views::TextView;
+    /// # use cursive_tabs::TabPanel;
+    /// let mut panel = TabPanel::default()
+    ///     .with_tab_with_id(1, TextView::new("First host"))
+    ///     .with_tab_with_id(2, TextView::new("Second host"));
+    /// panel.set_tab_title(&2, "db-2").expect("Id not found");
+    /// assert_eq!(panel.tab_title(&1), Some("1"));
+    /// ```
+    pub fn add_tab_with_id<T: View>(&mut self, id: K, view: T)
+    where
+        K: Display,
+    {
+        self.tabs.add_tab_with_id(id, view);
+        self.sync_bar();
+    }
+
+    /// Consuming & Chainable variant to add a new tab with the given id.
+    /// Note: Calls `add_tab_with_id` on the enclosed `TabView`.
+    pub fn with_tab_with_id<T: View>(mut self, id: K, view: T) -> Self
+    where
+        K: Display,
+    {
+        self.add_tab_with_id(id, view);
+        self
+    }
+
+    /// Non-consuming variant to add a new tab with the given id at a certain position, the view does not need to be named.
+    /// It is fail-safe, if the postion is greater than the amount of tabs, it is appended to the end.
+    /// Note: Calls `add_tab_with_id_at` on the enclosed `TabView`.
+    pub fn add_tab_with_id_at<T: View>(&mut self, id: K, view: T, pos: usize)
+    where
+        K: Display,
+    {
+        self.tabs.add_tab_with_id_at(id, view, pos);
+        self.sync_bar();
+    }
+
+    /// Consuming & Chainable variant to add a new tab with the given id at a certain position.
+    /// Note: Calls `add_tab_with_id_at` on the enclosed `TabView`.
+    pub fn with_tab_with_id_at<T: View>(mut self, id: K, view: T, pos: usize) -> Self
+    where
+        K: Display,
+    {
+        self.add_tab_with_id_at(id, view, pos);
         self
     }
 
     /// Remove a tab of the enclosed `TabView`.
-    pub fn remove_tab(&mut self, id: &str) -> Result<(), error::IdNotFound> {
-        self.bar.remove_button(id);
-        self.tabs.remove_tab(id)
+    /// Children of the removed tab are moved to its parent.
+    pub fn remove_tab<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.remove_tab_with(id, ChildPolicy::Reparent)
+    }
+
+    /// Remove a tab of the enclosed `TabView`, the policy decides whether its children are closed as well.
+    /// Note: Calls `remove_tab_with` on the enclosed `TabView`.
+    pub fn remove_tab_with<Q>(
+        &mut self,
+        id: &Q,
+        children: ChildPolicy,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.remove_tab_with(id, children)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Apply many changes to the tabs at once, the bar is brought up to date only once afterwards.
+    /// The batch always ends with an active tab, as long as there is a visible one.
+    /// Note: Calls `update` on the enclosed `TabView`.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::views::TextView;
+    /// # use cursive_tabs::TabPanel;
+    /// let mut panel = TabPanel::new();
+    /// panel.update(|tabs| {
+    ///     for host in 0..20 {
+    ///         tabs.add_tab_with_id(format!("host-{}", host), TextView::new("Connected"));
+    ///         tabs.set_tab_group(&format!("host-{}", host), Some("Hosts"))
+    ///             .expect("Id not found");
+    ///     }
+    /// });
+    /// assert_eq!(panel.len(), 20);
+    /// ```
+    pub fn update<R>(&mut self, changes: impl FnOnce(&mut TabView<K>) -> R) -> R {
+        let result = self.tabs.update(changes);
+        self.sync_bar();
+        result
     }
 
     /// Proceeds to the next view in order of addition.
     pub fn next(&mut self) {
-        self.tabs.next()
+        self.tabs.next();
+        self.sync_bar();
     }
 
     /// Go back to the previous view in order of addition.
     pub fn prev(&mut self) {
-        self.tabs.prev()
+        self.tabs.prev();
+        self.sync_bar();
     }
 
     /// Consumable & Chainable variant to set the bar alignment.
@@ -209,89 +797,775 @@ impl TabPanel {
         self.bar.set_alignment(align);
     }
 
+    /// Consumable & Chainable variant to set the bar placement.
     pub fn with_bar_placement(mut self, placement: Placement) -> Self {
         self.set_bar_placement(placement);
         self
     }
 
+    /// Non-consuming variant to set the bar placement.
     pub fn set_bar_placement(&mut self, placement: Placement) {
         self.bar_placement = placement;
         self.bar.set_placement(placement);
     }
 
+    /// Returns the order and state of the tabs, shared by the bar and the enclosed `TabView`.
+    /// Note: Calls `model` on the enclosed `TabView`.
+    pub fn model(&self) -> &TabModel<K> {
+        self.tabs.model()
+    }
+
     /// Returns the current order of tabs as an Vector with the keys of the views.
-    pub fn tab_order(&self) -> Vec<String> {
+    pub fn tab_order(&self) -> Vec<K> {
         self.tabs.tab_order()
     }
 
+    /// Returns the current order of tabs as an Vector with the keys of the views, leaving out hidden tabs.
+    pub fn visible_tab_order(&self) -> Vec<K> {
+        self.tabs.visible_tab_order()
+    }
+
+    /// Non-consuming variant to set the title shown for a tab in the bar.
+    /// Note: Calls `set_tab_title` on the enclosed `TabView`.
+    pub fn set_tab_title<Q>(
+        &mut self,
+        id: &Q,
+        title: impl Into<String>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_tab_title(id, title)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Consuming & Chainable variant to set the title shown for a tab in the bar.
+    /// Note: Calls `set_tab_title` on the enclosed `TabView`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_title<Q>(mut self, id: &Q, title: impl Into<String>) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_title(id, title) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the title shown for the given tab.
+    pub fn tab_title<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_title(id)
+    }
+
+    /// Attach data of any type to a tab, which is dropped once the tab is removed.
+    /// Note: Calls `set_tab_data` on the enclosed `TabView`.
+    pub fn set_tab_data<T, Q>(&mut self, id: &Q, data: T) -> Result<(), error::IdNotFound<K>>
+    where
+        T: Any + Send + Sync,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_tab_data(id, data)
+    }
+
+    /// Returns the data attached to the given tab, if it is of the requested type.
+    pub fn tab_data<T, Q>(&self, id: &Q) -> Option<&T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_data(id)
+    }
+
+    /// Returns a mutable reference to the data attached to the given tab, if it is of the requested type.
+    pub fn tab_data_mut<T, Q>(&mut self, id: &Q) -> Option<&mut T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_data_mut(id)
+    }
+
+    /// Removes the data attached to the given tab and returns it, if it is of the requested type.
+    pub fn take_tab_data<T, Q>(&mut self, id: &Q) -> Option<T>
+    where
+        T: Any,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.take_tab_data(id)
+    }
+
+    /// Non-consuming variant to show or hide a tab.
+    /// Hidden tabs are removed from the bar and skipped by `next` and `prev`, but their view is kept.
+    /// Note: Calls `set_tab_visible` on the enclosed `TabView`.
+    pub fn set_tab_visible<Q>(&mut self, id: &Q, visible: bool) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_tab_visible(id, visible)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Consuming & Chainable variant to show or hide a tab.
+    /// Note: Calls `set_tab_visible` on the enclosed `TabView`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_visible<Q>(mut self, id: &Q, visible: bool) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_visible(id, visible) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns whether the given tab is shown in the bar.
+    pub fn is_tab_visible<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.is_tab_visible(id)
+    }
+
+    /// Non-consuming variant to assign a tab to a named group, `None` removes it from its group.
+    /// Tabs of a group are kept next to each other and are preceded by the group label in the bar.
+    /// Children belong to the group of their top level tab.
+    /// Note: Calls `set_tab_group` on the enclosed `TabView`.
+    pub fn set_tab_group<Q>(
+        &mut self,
+        id: &Q,
+        group: Option<&str>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_tab_group(id, group)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Consuming & Chainable variant to assign a tab to a named group.
+    /// Note: Calls `set_tab_group` on the enclosed `TabView`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_group<Q>(mut self, id: &Q, group: Option<&str>) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_group(id, group) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the name of the group the given tab belongs to.
+    pub fn tab_group<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_group(id)
+    }
+
+    /// Non-consuming variant to make a tab the child of another tab, `None` makes it a top level tab.
+    /// Vertical bars draw children indented below their parent, with the children of
+    /// the tab under the cursor being folded and unfolded with the `Left` and `Right` keys.
+    /// Note: Calls `set_tab_parent` on the enclosed `TabView`.
+    pub fn set_tab_parent<Q>(
+        &mut self,
+        id: &Q,
+        parent: Option<&Q>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.set_tab_parent(id, parent)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Consuming & Chainable variant to make a tab the child of another tab.
+    /// Note: Calls `set_tab_parent` on the enclosed `TabView`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_parent<Q>(mut self, id: &Q, parent: Option<&Q>) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_parent(id, parent) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the id of the parent of the given tab.
+    pub fn tab_parent<Q>(&self, id: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_parent(id)
+    }
+
+    /// Returns the ids of the direct children of the given tab in tab order.
+    pub fn tab_children<Q>(&self, id: &Q) -> Vec<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.tabs.tab_children(id)
+    }
+
+    /// Mark an inactive tab as having new content, it is highlighted in the bar until it is activated again.
+    /// Note: Calls `notify_activity` on the enclosed `TabView`.
+    pub fn notify_activity<Q>(&mut self, id: &Q) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tabs.notify_activity(id)?;
+        self.sync_bar();
+        Ok(())
+    }
+
+    /// Returns the ids of all tabs with activity since they were last active, in tab order.
+    /// Besides `notify_activity`, inactive tabs are marked automatically when their view requests a relayout.
+    pub fn tabs_with_activity(&self) -> Vec<K> {
+        self.tabs.tabs_with_activity()
+    }
+
+    /// Enable or disable the automatic detection of activity on inactive tabs, enabled by default.
+    /// Note: Calls `set_activity_polling` on the enclosed `TabView`.
+    pub fn set_activity_polling(&mut self, enabled: bool) {
+        self.tabs.set_activity_polling(enabled);
+    }
+
+    /// Consumable & Chainable variant to enable or disable the automatic detection of activity.
+    pub fn with_activity_polling(mut self, enabled: bool) -> Self {
+        self.set_activity_polling(enabled);
+        self
+    }
+
+    /// Show a busy spinner or a percentage next to the title of a tab, `Progress::None` removes it.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::{Progress, TabPanel};
+    /// let mut siv = cursive::default();
+    /// // Redraw regularly to keep the spinner moving
+    /// siv.set_fps(10);
+    /// siv.add_layer(
+    ///     TabPanel::new()
+    ///         .with_tab(TextView::new("Building...").with_name("Build"))
+    ///         .with_tab_progress("Build", Progress::Spinner)
+    ///         .unwrap_or_else(|_| panic!("Build has been added")),
+    /// );
+    /// ```
+    pub fn set_tab_progress<Q>(
+        &mut self,
+        id: &Q,
+        progress: Progress,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if !self.tabs.contains(id) {
+            return Err(error::IdNotFound { id: id.to_owned() });
+        }
+        self.bar.set_progress(id, progress);
+        Ok(())
+    }
+
+    /// Consumable & Chainable variant to set the progress of a tab.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_progress<Q>(mut self, id: &Q, progress: Progress) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_progress(id, progress) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the progress currently shown for the given tab.
+    pub fn tab_progress<Q>(&self, id: &Q) -> Progress
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.progress(id)
+    }
+
+    /// Set the status of a tab, which changes the color of its title in the bar.
+    /// Inactive tabs use the dark variant of the status color, the active tab the light one.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    pub fn set_tab_status<Q>(&mut self, id: &Q, status: Status) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if !self.tabs.contains(id) {
+            return Err(error::IdNotFound { id: id.to_owned() });
+        }
+        self.bar.set_status(id, status);
+        Ok(())
+    }
+
+    /// Consumable & Chainable variant to set the status of a tab.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_status<Q>(mut self, id: &Q, status: Status) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_status(id, status) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the status of the given tab.
+    pub fn tab_status<Q>(&self, id: &Q) -> Status
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.status(id)
+    }
+
+    /// Set the minimum width of every tab in the bar, shorter titles are centered.
+    pub fn set_tab_min_width(&mut self, width: usize) {
+        self.bar.set_min_width(width);
+    }
+
+    /// Consumable & Chainable variant to set the minimum width of the tabs.
+    pub fn with_tab_min_width(mut self, width: usize) -> Self {
+        self.set_tab_min_width(width);
+        self
+    }
+
+    /// Set the maximum width of every tab in the bar, `None` removes the limit.
+    ///
+    /// Longer titles are cut off with an ellipsis. The full title of the tab below the cursor
+    /// is available through `cursor_tab_title`, e.g. to show it in a status line.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabPanel;
+    /// let panel = TabPanel::new()
+    ///     .with_tab(TextView::new("Logs").with_name("A rather long title"))
+    ///     .with_tab_max_width(Some(12));
+    /// ```
+    pub fn set_tab_max_width(&mut self, width: Option<usize>) {
+        self.bar.set_max_width(width);
+    }
+
+    /// Consumable & Chainable variant to set the maximum width of the tabs.
+    pub fn with_tab_max_width(mut self, width: Option<usize>) -> Self {
+        self.set_tab_max_width(width);
+        self
+    }
+
+    /// Draw all tabs with the width of the widest one, within the minimum and maximum width.
+    pub fn set_equal_tab_widths(&mut self, equal: bool) {
+        self.bar.set_equal_width(equal);
+    }
+
+    /// Consumable & Chainable variant to draw all tabs with the same width.
+    pub fn with_equal_tab_widths(mut self, equal: bool) -> Self {
+        self.set_equal_tab_widths(equal);
+        self
+    }
+
+    /// Set the number of blank cells on both sides of every title in the bar, defaults to 1.
+    pub fn set_tab_padding(&mut self, padding: usize) {
+        self.bar.set_padding(padding);
+    }
+
+    /// Consumable & Chainable variant to set the padding of the titles.
+    pub fn with_tab_padding(mut self, padding: usize) -> Self {
+        self.set_tab_padding(padding);
+        self
+    }
+
+    /// Set the separators drawn between and around the tabs of the bar.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::{Separator, TabPanel};
+    /// let panel = TabPanel::new()
+    ///     .with_tab(TextView::new("Shopping list").with_name("Shop"))
+    ///     .with_tab(TextView::new("Recipes").with_name("Cook"))
+    ///     .with_tab_separator(Separator::Text("|".into(), "#".into()));
+    /// ```
+    pub fn set_tab_separator(&mut self, separator: Separator) {
+        self.bar.set_separator(separator);
+    }
+
+    /// Consumable & Chainable variant to set the separators of the bar.
+    pub fn with_tab_separator(mut self, separator: Separator) -> Self {
+        self.set_tab_separator(separator);
+        self
+    }
+
+    /// Returns the separators drawn in the bar.
+    pub fn tab_separator(&self) -> &Separator {
+        self.bar.separator()
+    }
+
+    /// Set the number of cells between two tabs, defaults to 0.
+    ///
+    /// With a gap every tab is enclosed by its own separators, otherwise neighbours share one.
+    pub fn set_tab_gap(&mut self, gap: usize) {
+        self.bar.set_gap(gap);
+    }
+
+    /// Consumable & Chainable variant to set the gap between tabs.
+    pub fn with_tab_gap(mut self, gap: usize) -> Self {
+        self.set_tab_gap(gap);
+        self
+    }
+
+    /// Returns whether the title of the given tab is currently cut off in the bar.
+    pub fn is_tab_truncated<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.is_truncated(id)
+    }
+
+    /// Returns the id of the tab below the cursor of the bar.
+    pub fn cursor_tab(&self) -> Option<&K> {
+        self.bar.cursor_key()
+    }
+
+    /// Returns the full title of the tab below the cursor of the bar, even if it is cut off.
+    pub fn cursor_tab_title(&self) -> Option<&str> {
+        self.cursor_tab().and_then(|id| self.tabs.tab_title(id))
+    }
+
+    /// Show a short badge, like an unread counter or `"!"`, after the title of a tab in the bar.
+    /// `None` removes the badge again.
+    /// If the tab id is not known, an error is returned and no action is performed.
+    ///
+    /// Badges can be updated from other threads by going through the callback sink of cursive.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive::{view::Nameable, views::TextView};
+    /// # use cursive_tabs::TabPanel;
+    /// let mut siv = cursive::default();
+    /// siv.add_layer(
+    ///     TabPanel::new()
+    ///         .with_tab(TextView::new("Connecting...").with_name("Logs"))
+    ///         .with_name("tabs"),
+    /// );
+    ///
+    /// let sink = siv.cb_sink().clone();
+    /// std::thread::spawn(move || {
+    ///     let unread = 3;
+    ///     sink.send(Box::new(move |siv| {
+    ///         siv.call_on_name("tabs", |tabs: &mut TabPanel| {
+    ///             tabs.set_tab_badge("Logs", Some(&unread.to_string()))
+    ///                 .expect("Logs has been added");
+    ///         });
+    ///     }))
+    ///     .expect("cursive has been stopped");
+    /// });
+    /// ```
+    pub fn set_tab_badge<Q>(
+        &mut self,
+        id: &Q,
+        badge: Option<&str>,
+    ) -> Result<(), error::IdNotFound<K>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if !self.tabs.contains(id) {
+            return Err(error::IdNotFound { id: id.to_owned() });
+        }
+        self.bar.set_badge(id, badge);
+        Ok(())
+    }
+
+    /// Consumable & Chainable variant to set the badge of a tab.
+    #[allow(clippy::result_large_err)]
+    pub fn with_tab_badge<Q>(mut self, id: &Q, badge: Option<&str>) -> Result<Self, Self>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.set_tab_badge(id, badge) {
+            Ok(_) => Ok(self),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Returns the badge currently shown for the given tab.
+    pub fn tab_badge<Q>(&self, id: &Q) -> Option<&str>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.badge(id)
+    }
+
+    /// Fold the children of a tab into it in the bar, or unfold them again.
+    pub fn set_tab_collapsed<Q>(&mut self, id: &Q, collapsed: bool)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.set_tab_collapsed(id, collapsed);
+    }
+
+    /// Consumable & Chainable variant to fold or unfold the children of a tab.
+    pub fn with_tab_collapsed<Q>(mut self, id: &Q, collapsed: bool) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.set_tab_collapsed(id, collapsed);
+        self
+    }
+
+    /// Returns whether the children of the given tab are folded in the bar.
+    pub fn is_tab_collapsed<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.bar.is_tab_collapsed(id)
+    }
+
+    /// Collapse a group into a single entry in the bar, or expand it again.
+    /// Clicking the group label in the bar toggles this as well.
+    pub fn set_group_collapsed(&mut self, group: &str, collapsed: bool) {
+        self.bar.set_group_collapsed(group, collapsed);
+    }
+
+    /// Consumable & Chainable variant to collapse or expand a group.
+    pub fn with_group_collapsed(mut self, group: &str, collapsed: bool) -> Self {
+        self.set_group_collapsed(group, collapsed);
+        self
+    }
+
+    /// Returns whether the given group is currently collapsed in the bar.
+    pub fn is_group_collapsed(&self, group: &str) -> bool {
+        self.bar.is_group_collapsed(group)
+    }
+
+    /// Set the color the label of a group is drawn with.
+    /// By default the tertiary color of the current theme is used.
+    pub fn set_group_color(&mut self, group: &str, color: Color) {
+        self.bar.set_group_color(group, color);
+    }
+
+    /// Consumable & Chainable variant to set the color of a group label.
+    pub fn with_group_color(mut self, group: &str, color: Color) -> Self {
+        self.set_group_color(group, color);
+        self
+    }
+
+    /// Set the styles used to draw the tab bar and the border of the panel.
+    pub fn set_style(&mut self, style: TabStyle) {
+        self.bar.set_style(style);
+    }
+
+    /// Consumable & Chainable variant to set the styles of the panel.
+    pub fn with_style(mut self, style: TabStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    /// Returns the styles used to draw the tab bar and the border of the panel.
+    pub fn style(&self) -> &TabStyle {
+        self.bar.style()
+    }
+
+    /// Set the characters used for the border of the panel and the separators in the bar.
+    ///
+    /// # Example
+    /// ```
+    /// # use cursive_tabs::{BorderStyle, TabPanel};
+    /// let panel = TabPanel::new().with_border_style(BorderStyle::Ascii);
+    /// ```
+    pub fn set_border_style(&mut self, border: BorderStyle) {
+        self.bar.set_border_style(border);
+    }
+
+    /// Consumable & Chainable variant to set the border style of the panel.
+    pub fn with_border_style(mut self, border: BorderStyle) -> Self {
+        self.set_border_style(border);
+        self
+    }
+
+    /// Returns the characters currently used for the border of the panel.
+    pub fn border_style(&self) -> BorderStyle {
+        self.bar.border_style()
+    }
+
+    /// Omit the outer frame of the panel, e.g. when it is placed inside a `Dialog` or `Panel`
+    /// which already draws one. Only the line of the bar separating it from the content remains.
+    pub fn set_borderless(&mut self, borderless: bool) {
+        self.borderless = borderless;
+    }
+
+    /// Consumable & Chainable variant to omit the outer frame of the panel.
+    pub fn with_borderless(mut self, borderless: bool) -> Self {
+        self.set_borderless(borderless);
+        self
+    }
+
+    /// Returns whether the outer frame of the panel is omitted.
+    pub fn is_borderless(&self) -> bool {
+        self.borderless
+    }
+
+    // Width of the outer frame
+    fn border(&self) -> usize {
+        if self.borderless {
+            0
+        } else {
+            1
+        }
+    }
+
+    // Position of the content relative to the panel
+    fn tab_offset(&self) -> Vec2 {
+        let border = self.border();
+        match self.bar_placement {
+            Placement::HorizontalTop => Vec2::new(border, self.bar_size.y),
+            Placement::VerticalLeft => Vec2::new(self.bar_size.x, border),
+            Placement::HorizontalBottom | Placement::VerticalRight => Vec2::new(border, border),
+        }
+    }
+
+    // Position of the bar relative to the panel, only valid after the layout
+    fn bar_offset(&self) -> Vec2 {
+        let border = self.border();
+        match self.bar_placement {
+            Placement::HorizontalTop => Vec2::new(border, 0),
+            Placement::VerticalLeft => Vec2::new(0, border),
+            Placement::HorizontalBottom => Vec2::new(border, border + self.tab_size.y),
+            Placement::VerticalRight => Vec2::new(border + self.tab_size.x, border),
+        }
+    }
+
+    // Bring the bar up to date with the tabs of the view
+    fn sync_bar(&mut self) {
+        self.bar.sync(self.tabs.model());
+    }
+
     // Print lines corresponding to the current placement
     fn draw_outer_panel(&self, printer: &Printer) {
+        let glyphs = self.bar.border_style().glyphs();
         match self.bar_placement {
             Placement::HorizontalTop => {
                 // Side bars
-                printer.print_vline((0, 0), printer.size.y, "│");
-                printer.print_vline((printer.size.x - 1, 0), printer.size.y, "│");
+                printer.print_vline((0, 0), printer.size.y, glyphs.vertical);
+                printer.print_vline((printer.size.x - 1, 0), printer.size.y, glyphs.vertical);
                 // Bottom line
-                printer.print_hline((0, printer.size.y - 1), printer.size.x, "─");
+                printer.print_hline((0, printer.size.y - 1), printer.size.x, glyphs.horizontal);
 
-                printer.print((0, self.bar_size.y - 1), "┌");
-                printer.print((printer.size.x - 1, self.bar_size.y - 1), "┐");
-                printer.print((0, printer.size.y - 1), "└");
-                printer.print((printer.size.x - 1, printer.size.y - 1), "┘");
+                printer.print((0, self.bar_size.y - 1), glyphs.top_left);
+                printer.print((printer.size.x - 1, self.bar_size.y - 1), glyphs.top_right);
+                printer.print((0, printer.size.y - 1), glyphs.bottom_left);
+                printer.print(
+                    (printer.size.x - 1, printer.size.y - 1),
+                    glyphs.bottom_right,
+                );
             }
             Placement::HorizontalBottom => {
                 // Side bars
-                printer.print_vline((0, 0), printer.size.y, "│");
-                printer.print_vline((printer.size.x - 1, 0), printer.size.y, "│");
+                printer.print_vline((0, 0), printer.size.y, glyphs.vertical);
+                printer.print_vline((printer.size.x - 1, 0), printer.size.y, glyphs.vertical);
                 // Top line
                 let lowest = clamp(printer.size.y - self.bar_size.y, 0, printer.size.y - 1);
-                printer.print_hline((0, 0), printer.size.x, "─");
-                printer.print((0, 0), "┌");
-                printer.print((printer.size.x - 1, 0), "┐");
-                printer.print((0, lowest), "└");
-                printer.print((printer.size.x - 1, lowest), "┘");
+                printer.print_hline((0, 0), printer.size.x, glyphs.horizontal);
+                printer.print((0, 0), glyphs.top_left);
+                printer.print((printer.size.x - 1, 0), glyphs.top_right);
+                printer.print((0, lowest), glyphs.bottom_left);
+                printer.print((printer.size.x - 1, lowest), glyphs.bottom_right);
             }
             Placement::VerticalLeft => {
                 // Side bar
-                printer.print_vline((printer.size.x - 1, 0), printer.size.y, "│");
+                printer.print_vline((printer.size.x - 1, 0), printer.size.y, glyphs.vertical);
                 // Top lines
-                printer.print_hline((self.bar_size.x - 1, 0), printer.size.x, "─");
+                printer.print_hline((self.bar_size.x - 1, 0), printer.size.x, glyphs.horizontal);
                 printer.print_hline(
                     (self.bar_size.x - 1, printer.size.y - 1),
                     printer.size.x,
-                    "─",
+                    glyphs.horizontal,
+                );
+                printer.print((self.bar_size.x - 1, 0), glyphs.top_left);
+                printer.print((printer.size.x - 1, 0), glyphs.top_right);
+                printer.print(
+                    (self.bar_size.x - 1, printer.size.y - 1),
+                    glyphs.bottom_left,
+                );
+                printer.print(
+                    (printer.size.x - 1, printer.size.y - 1),
+                    glyphs.bottom_right,
                 );
-                printer.print((self.bar_size.x - 1, 0), "┌");
-                printer.print((printer.size.x - 1, 0), "┐");
-                printer.print((self.bar_size.x - 1, printer.size.y - 1), "└");
-                printer.print((printer.size.x - 1, printer.size.y - 1), "┘");
             }
             Placement::VerticalRight => {
                 // Side bar
-                printer.print_vline((0, 0), printer.size.y, "│");
+                printer.print_vline((0, 0), printer.size.y, glyphs.vertical);
                 // Top lines
-                printer.print_hline((0, 0), printer.size.x, "─");
+                printer.print_hline((0, 0), printer.size.x, glyphs.horizontal);
                 // Line draws too far here, needs to be overwritten with blanks
-                printer.print_hline((0, printer.size.y - 1), printer.size.x, "─");
+                printer.print_hline((0, printer.size.y - 1), printer.size.x, glyphs.horizontal);
 
                 let right = clamp(printer.size.x - self.bar_size.x, 0, printer.size.x - 1);
-                printer.print((0, 0), "┌");
-                printer.print((right, 0), "┐");
+                printer.print((0, 0), glyphs.top_left);
+                printer.print((right, 0), glyphs.top_right);
                 printer.print_hline((right + 1, 0), printer.size.x, " ");
-                printer.print((0, printer.size.y - 1), "└");
-                printer.print((right, printer.size.y - 1), "┘");
+                printer.print((0, printer.size.y - 1), glyphs.bottom_left);
+                printer.print((right, printer.size.y - 1), glyphs.bottom_right);
                 printer.print_hline((right + 1, printer.size.y - 1), printer.size.x, " ");
             }
         }
     }
+}
 
+impl<K: Eq + Hash + Clone + Send + Sync + 'static> TabPanel<K> {
     fn on_event_focused(&mut self, evt: Event) -> EventResult {
-        match self.bar.on_event(evt.relativized(match self.bar_placement {
-            Placement::HorizontalTop | Placement::VerticalLeft => Vec2::new(0, 0),
-            Placement::HorizontalBottom => self.tab_size.keep_y() + Vec2::new(0, 1),
-            Placement::VerticalRight => self.tab_size.keep_x() + Vec2::new(1, 0),
-        })) {
-            EventResult::Consumed(cb) => EventResult::Consumed(cb),
+        match self.bar.on_event(evt.relativized(self.bar_offset())) {
+            EventResult::Consumed(cb) => {
+                if let Some(id) = self.bar.take_selected() {
+                    if self.tabs.set_active_tab(&id).is_err() {
+                        debug!("could not activate tab selected in the bar, it has been removed");
+                    }
+                    self.sync_bar();
+                }
+                EventResult::Consumed(cb)
+            }
             EventResult::Ignored => match evt {
                 Event::Key(Key::Down) if self.bar_placement == Placement::HorizontalTop => {
                     if let Ok(result) = self.tabs.take_focus(Direction::up()) {
@@ -331,13 +1605,7 @@ impl TabPanel {
     }
 
     fn on_event_unfocused(&mut self, evt: Event) -> EventResult {
-        match self
-            .tabs
-            .on_event(evt.relativized(match self.bar_placement {
-                Placement::HorizontalTop => Vec2::new(1, self.bar_size.y),
-                Placement::VerticalLeft => Vec2::new(self.bar_size.x, 1),
-                Placement::HorizontalBottom | Placement::VerticalRight => Vec2::new(1, 1),
-            })) {
+        match self.tabs.on_event(evt.relativized(self.tab_offset())) {
             EventResult::Consumed(cb) => EventResult::Consumed(cb),
             EventResult::Ignored => match evt {
                 Event::Key(Key::Up) if self.bar_placement == Placement::HorizontalTop => {
@@ -378,7 +1646,8 @@ impl TabPanel {
 
             match self.bar_placement {
                 Placement::VerticalRight | Placement::HorizontalBottom => {
-                    if position > offset && self.tab_size.fits(position - offset) {
+                    let origin = offset + self.tab_offset();
+                    if position.fits(origin) && self.tab_size.fits(position - origin) {
                         if let Ok(res) = self.tabs.take_focus(Direction::none()) {
                             self.bar_focused = false;
                             return res;
@@ -405,71 +1674,55 @@ impl TabPanel {
     }
 }
 
-impl View for TabPanel {
+impl<K: Eq + Hash + Clone + Send + Sync + 'static> View for TabPanel<K> {
     fn draw(&self, printer: &Printer) {
-        self.draw_outer_panel(printer);
+        if !self.borderless {
+            printer.with_style(self.bar.style().border, |printer| {
+                self.draw_outer_panel(printer)
+            });
+        }
+        let border = self.border();
         let printer_bar = printer
-            .offset(match self.bar_placement {
-                Placement::HorizontalTop => (1, 0),
-                Placement::HorizontalBottom => (
-                    1,
-                    clamp(printer.size.y - self.bar_size.y, 0, printer.size.y - 1),
-                ),
-                Placement::VerticalLeft => (0, 1),
-                Placement::VerticalRight => (
-                    clamp(printer.size.x - self.bar_size.x, 0, printer.size.x - 1),
-                    1,
-                ),
-            })
+            .offset(self.bar_offset())
             .cropped(match self.bar_placement {
                 Placement::HorizontalTop | Placement::HorizontalBottom => {
-                    (printer.size.x - 2, self.bar_size.y)
+                    (printer.size.x - 2 * border, self.bar_size.y)
                 }
                 Placement::VerticalRight | Placement::VerticalLeft => {
-                    (self.bar_size.x, printer.size.y - 2)
+                    (self.bar_size.x, printer.size.y - 2 * border)
                 }
             })
             .focused(self.bar_focused);
         let printer_tab = printer
-            .offset(match self.bar_placement {
-                Placement::VerticalLeft => (self.bar_size.x, 1),
-                Placement::VerticalRight => (1, 1),
-                Placement::HorizontalBottom => (1, 1),
-                Placement::HorizontalTop => (1, self.bar_size.y),
-            })
+            .offset(self.tab_offset())
             // Inner area
-            .cropped(match self.bar_placement {
-                Placement::VerticalLeft | Placement::VerticalRight => {
-                    (printer.size.x - self.bar_size.x - 1, printer.size.y - 2)
-                }
-                Placement::HorizontalBottom | Placement::HorizontalTop => {
-                    (printer.size.x - 2, printer.size.y - self.bar_size.y - 1)
-                }
-            })
+            .cropped(self.tab_size)
             .focused(!self.bar_focused);
         self.bar.draw(&printer_bar);
         self.tabs.draw(&printer_tab);
     }
 
     fn layout(&mut self, vec: Vec2) {
+        let border = self.border();
         self.bar.layout(match self.bar_placement {
             Placement::VerticalRight | Placement::VerticalLeft => {
-                Vec2::new(self.bar_size.x, vec.y - 2)
+                Vec2::new(self.bar_size.x, vec.y - 2 * border)
             }
             Placement::HorizontalBottom | Placement::HorizontalTop => {
-                Vec2::new(vec.x - 2, self.bar_size.y)
+                Vec2::new(vec.x - 2 * border, self.bar_size.y)
             }
         });
-        self.tabs.layout(match self.bar_placement {
+        self.tab_size = match self.bar_placement {
             Placement::VerticalRight | Placement::VerticalLeft => {
-                self.tab_size = Vec2::new(vec.x - self.bar_size.x - 1, vec.y - 2);
-                self.tab_size
+                Vec2::new(vec.x - self.bar_size.x - border, vec.y - 2 * border)
             }
             Placement::HorizontalBottom | Placement::HorizontalTop => {
-                self.tab_size = Vec2::new(vec.x - 2, vec.y - self.bar_size.y - 1);
-                self.tab_size
+                Vec2::new(vec.x - 2 * border, vec.y - self.bar_size.y - border)
             }
-        });
+        };
+        self.tabs.layout(self.tab_size);
+        // Polling may have found activity on inactive tabs
+        self.sync_bar();
     }
 
     fn needs_relayout(&self) -> bool {
@@ -477,17 +1730,19 @@ impl View for TabPanel {
     }
 
     fn required_size(&mut self, cst: Vec2) -> Vec2 {
+        let border = self.border();
         let tab_size = self.tabs.required_size(cst);
+        self.sync_bar();
         self.bar_size = self.bar.required_size(cst);
         match self.bar_placement {
             Placement::HorizontalTop | Placement::HorizontalBottom => self
                 .bar_size
                 .stack_vertical(&tab_size)
-                .stack_vertical(&Vec2::new(tab_size.x + 2, 1)),
+                .stack_vertical(&Vec2::new(tab_size.x + 2 * border, border)),
             Placement::VerticalLeft | Placement::VerticalRight => self
                 .bar_size
                 .stack_horizontal(&tab_size)
-                .stack_vertical(&Vec2::new(1, tab_size.y + 2)),
+                .stack_vertical(&Vec2::new(1, tab_size.y + 2 * border)),
         }
     }
 
@@ -502,14 +1757,10 @@ impl View for TabPanel {
     }
 
     fn take_focus(&mut self, d: Direction) -> Result<EventResult, CannotFocus> {
-        let tabs_take_focus = |panel: &mut TabPanel, d: Direction| {
+        let tabs_take_focus = |panel: &mut Self, d: Direction| {
             let result = panel.tabs.take_focus(d);
 
-            if result.is_ok() {
-                panel.bar_focused = false;
-            } else {
-                panel.bar_focused = true;
-            }
+            panel.bar_focused = result.is_err();
 
             result
         };
@@ -521,10 +1772,10 @@ impl View for TabPanel {
                 Direction::Abs(Absolute::Up) => {
                     result = tabs_take_focus(self, d);
                 }
-                Direction::Abs(Absolute::Left) | Direction::Abs(Absolute::Right) => {
-                    if !self.bar_focused {
-                        result = tabs_take_focus(self, d);
-                    }
+                Direction::Abs(Absolute::Left) | Direction::Abs(Absolute::Right)
+                    if !self.bar_focused =>
+                {
+                    result = tabs_take_focus(self, d);
                 }
                 Direction::Abs(Absolute::Down) => {
                     self.bar_focused = true;
@@ -535,10 +1786,10 @@ impl View for TabPanel {
                 Direction::Abs(Absolute::Down) => {
                     result = tabs_take_focus(self, d);
                 }
-                Direction::Abs(Absolute::Left) | Direction::Abs(Absolute::Right) => {
-                    if !self.bar_focused {
-                        result = tabs_take_focus(self, d);
-                    }
+                Direction::Abs(Absolute::Left) | Direction::Abs(Absolute::Right)
+                    if !self.bar_focused =>
+                {
+                    result = tabs_take_focus(self, d);
                 }
                 Direction::Abs(Absolute::Up) => {
                     self.bar_focused = true;
@@ -549,10 +1800,10 @@ impl View for TabPanel {
                 Direction::Abs(Absolute::Right) => {
                     result = tabs_take_focus(self, d);
                 }
-                Direction::Abs(Absolute::Up) | Direction::Abs(Absolute::Down) => {
-                    if !self.bar_focused {
-                        result = tabs_take_focus(self, d);
-                    }
+                Direction::Abs(Absolute::Up) | Direction::Abs(Absolute::Down)
+                    if !self.bar_focused =>
+                {
+                    result = tabs_take_focus(self, d);
                 }
                 Direction::Abs(Absolute::Left) => self.bar_focused = true,
                 _ => {}
@@ -561,17 +1812,17 @@ impl View for TabPanel {
                 Direction::Abs(Absolute::Left) => {
                     result = tabs_take_focus(self, d);
                 }
-                Direction::Abs(Absolute::Up) | Direction::Abs(Absolute::Down) => {
-                    if !self.bar_focused {
-                        result = tabs_take_focus(self, d)
-                    }
+                Direction::Abs(Absolute::Up) | Direction::Abs(Absolute::Down)
+                    if !self.bar_focused =>
+                {
+                    result = tabs_take_focus(self, d);
                 }
                 Direction::Abs(Absolute::Right) => self.bar_focused = true,
                 _ => {}
             },
         }
 
-        return Ok(result.unwrap_or(EventResult::Ignored));
+        Ok(result.unwrap_or(EventResult::Ignored))
     }
 
     fn focus_view(&mut self, slt: &Selector) -> Result<EventResult, ViewNotFound> {
@@ -1,12 +1,16 @@
 use crossbeam::channel::{Receiver, Sender};
-use cursive::backends::puppet::observed::ObservedScreen;
+use cursive::backends::puppet::observed::{ObservedPieceInterface, ObservedScreen};
 use cursive::backends::puppet::Backend;
-use cursive::event::{Event, Key};
-use cursive::view::Nameable;
+use cursive::event::{Event, Key, MouseButton, MouseEvent};
+use cursive::theme::{BaseColor, Color, ColorPair, ColorStyle};
+use cursive::view::{Nameable, Resizable};
 use cursive::views::TextView;
 use cursive::Vec2;
-use cursive_tabs::{Align, Placement, TabPanel, TabView};
-use insta::assert_display_snapshot;
+use cursive_tabs::{
+    Align, BorderStyle, ChildPolicy, Placement, Progress, Separator, Status, TabPanel, TabStyle,
+    TabView,
+};
+use insta::assert_snapshot;
 
 fn setup_test_environment<F>(cb: F) -> (Receiver<ObservedScreen>, Sender<Option<Event>>)
 where
@@ -72,6 +76,11 @@ impl TestCursive {
     }
 }
 
+fn color_of(screen: &ObservedScreen, text: &str) -> ColorPair {
+    let pos = screen.find_occurences(text)[0].min();
+    screen[pos].as_ref().expect("Cell not drawn").style.colors
+}
+
 #[test]
 fn test_puppet_screen() {
     let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
@@ -79,7 +88,7 @@ fn test_puppet_screen() {
             "This is a smoke test for the puppet cursive backend.",
         ))
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap())
+    assert_snapshot!(frames.try_iter().last().unwrap())
 }
 
 #[test]
@@ -91,7 +100,7 @@ fn end2end_add_at() {
             .with_tab_at(TextView::new("Second").with_name("2"), 1);
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -104,7 +113,7 @@ fn end2end_add_at_action_change_tab() {
         siv.add_layer(tabs);
     });
     tsiv.input(Event::Key(Key::Up));
-    assert_display_snapshot!(tsiv.last_screen());
+    assert_snapshot!(tsiv.last_screen());
 }
 
 #[test]
@@ -117,7 +126,7 @@ fn end2end_add_at_panel() {
             .with_bar_alignment(Align::Center);
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -130,7 +139,7 @@ fn end2end_panel_smoke() {
             .with_bar_alignment(Align::Center);
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -142,7 +151,7 @@ fn end2end_remove_active() {
         tabs.remove_tab("1").expect("Removal of active tab failed");
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -154,7 +163,7 @@ fn end2end_remove_inactive() {
         tabs.remove_tab("0").expect("Removal failed.");
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -168,7 +177,7 @@ fn end2end_swap() {
         tabs.swap_tabs("So", "Stonks");
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -181,7 +190,7 @@ fn end2end_switch() {
             .unwrap_or_else(|_| panic!("Setting active tab has failed"));
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -196,7 +205,7 @@ fn end2end_vertical_left() {
             .with_bar_placement(Placement::VerticalLeft);
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
 }
 
 #[test]
@@ -212,7 +221,7 @@ fn end2end_vertical_left_with_action_change_tab() {
         siv.add_layer(tabs);
     });
     tsiv.input(Event::Key(Key::Up));
-    assert_display_snapshot!(tsiv.last_screen());
+    assert_snapshot!(tsiv.last_screen());
 }
 
 #[test]
@@ -226,5 +235,757 @@ fn end2end_vertical_right() {
             .with_bar_placement(Placement::VerticalRight);
         siv.add_layer(tabs);
     });
-    assert_display_snapshot!(frames.try_iter().last().unwrap());
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_hidden() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab_visible("So", false)
+            .unwrap_or_else(|_| panic!("Hiding tab has failed"))
+            .with_tab_visible("Much", false)
+            .unwrap_or_else(|_| panic!("Hiding tab has failed"))
+            .with_bar_alignment(Align::Center);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_groups() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab(TextView::new("Wooooo").with_name("Wow"))
+            .with_tab_group("Stonks", Some("Logs"))
+            .unwrap_or_else(|_| panic!("Grouping tab has failed"))
+            .with_tab_group("Much", Some("Logs"))
+            .unwrap_or_else(|_| panic!("Grouping tab has failed"))
+            .with_tab_group("Wow", Some("Metrics"))
+            .unwrap_or_else(|_| panic!("Grouping tab has failed"))
+            .with_bar_alignment(Align::Center);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_groups_collapsed() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab_group("Stonks", Some("Logs"))
+            .unwrap_or_else(|_| panic!("Grouping tab has failed"))
+            .with_tab_group("So", Some("Logs"))
+            .unwrap_or_else(|_| panic!("Grouping tab has failed"))
+            .with_group_collapsed("Logs", true)
+            .with_bar_alignment(Align::Center);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_tree() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab(TextView::new("Wooooo").with_name("Wow"))
+            .with_tab_parent("So", Some("Stonks"))
+            .unwrap_or_else(|_| panic!("Setting parent has failed"))
+            .with_tab_parent("Much", Some("So"))
+            .unwrap_or_else(|_| panic!("Setting parent has failed"))
+            .with_tab_parent("Wow", Some("Stonks"))
+            .unwrap_or_else(|_| panic!("Setting parent has failed"))
+            .with_bar_placement(Placement::VerticalRight);
+        siv.add_layer(tabs.with_name("panel"));
+    });
+    assert_snapshot!(tsiv.last_screen());
+    tsiv.input(Event::Key(Key::Up));
+    tsiv.input(Event::Key(Key::Up));
+    tsiv.input(Event::Key(Key::Up));
+    tsiv.input(Event::Key(Key::Left));
+    assert_snapshot!(tsiv.last_screen());
+    tsiv._call_on(|siv| {
+        siv.call_on_name("panel", |panel: &mut TabPanel| {
+            panel.set_tab_collapsed("Stonks", false);
+            panel
+                .remove_tab_with("So", ChildPolicy::Close)
+                .expect("Removal failed.");
+        });
+    });
+    tsiv.step();
+    assert_snapshot!(tsiv.last_screen());
+}
+
+#[test]
+fn end2end_badge() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab_badge("Stonks", Some("3"))
+            .unwrap_or_else(|_| panic!("Setting a badge has failed"))
+            .with_bar_alignment(Align::Center);
+        tabs.set_tab_badge("Much", Some("!")).expect("Id not found");
+        assert!(tabs.set_tab_badge("Wow", Some("1")).is_err());
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_activity() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("Stonks")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"));
+        siv.add_layer(tabs.with_name("panel"));
+    });
+    tsiv._call_on(|siv| {
+        siv.call_on_name("panel", |panel: &mut TabPanel| {
+            panel.notify_activity("Much").expect("Id not found");
+        });
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    // The marked tab is drawn in a different color than the other inactive one
+    assert_ne!(color_of(&screen, "Much"), color_of(&screen, "So"));
+    assert_snapshot!(screen);
+}
+
+#[test]
+fn end2end_progress() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab_progress("Stonks", Progress::Percent(42))
+            .unwrap_or_else(|_| panic!("Setting the progress has failed"))
+            .with_bar_alignment(Align::Center);
+        tabs.set_tab_progress("So", Progress::Percent(100))
+            .expect("Id not found");
+        assert!(tabs.set_tab_progress("Wow", Progress::Spinner).is_err());
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_status() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_tab_status("So", Status::Error)
+            .unwrap_or_else(|_| panic!("Setting the status has failed"))
+            .with_active_tab("Stonks")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"));
+        tabs.set_tab_status("Stonks", Status::Success)
+            .expect("Id not found");
+        assert!(tabs.set_tab_status("Wow", Status::Warning).is_err());
+        siv.add_layer(tabs.with_name("panel"));
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    assert_eq!(color_of(&screen, "So").front, Color::Dark(BaseColor::Red));
+    assert_eq!(
+        color_of(&screen, "Stonks").front,
+        Color::Light(BaseColor::Green)
+    );
+    tsiv._call_on(|siv| {
+        siv.call_on_name("panel", |panel: &mut TabPanel| {
+            panel.set_active_tab("So").expect("Id not found");
+        });
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    // The active tab is drawn in the lighter variant of its status color
+    assert_eq!(color_of(&screen, "So").front, Color::Light(BaseColor::Red));
+    assert_eq!(
+        color_of(&screen, "Stonks").front,
+        Color::Dark(BaseColor::Green)
+    );
+}
+
+#[test]
+fn end2end_style() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("Stonks")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"))
+            .with_style(TabStyle {
+                active: ColorStyle::front(Color::Dark(BaseColor::Green)).into(),
+                inactive: ColorStyle::front(Color::Dark(BaseColor::Cyan)).into(),
+                border: ColorStyle::front(Color::Dark(BaseColor::Magenta)).into(),
+                ..TabStyle::default()
+            });
+        siv.add_layer(tabs);
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    assert_eq!(
+        color_of(&screen, "Stonks").front,
+        Color::Dark(BaseColor::Green)
+    );
+    assert_eq!(color_of(&screen, "So").front, Color::Dark(BaseColor::Cyan));
+    assert_eq!(
+        color_of(&screen, "└").front,
+        Color::Dark(BaseColor::Magenta)
+    );
+}
+
+#[test]
+fn end2end_border_ascii() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"))
+            .with_bar_placement(Placement::HorizontalTop)
+            .with_border_style(BorderStyle::Ascii);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_border_heavy() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"))
+            .with_bar_placement(Placement::VerticalRight)
+            .with_border_style(BorderStyle::Heavy);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_border_double() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"))
+            .with_bar_placement(Placement::VerticalLeft)
+            .with_border_style(BorderStyle::Double);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_border_rounded() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"))
+            .with_bar_placement(Placement::HorizontalBottom)
+            .with_border_style(BorderStyle::Rounded);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_borderless() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_borderless(true);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_mouse_select() {
+    for borderless in [false, true] {
+        for placement in [
+            Placement::HorizontalTop,
+            Placement::HorizontalBottom,
+            Placement::VerticalLeft,
+            Placement::VerticalRight,
+        ] {
+            let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+                let tabs = TabPanel::new()
+                    .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+                    .with_tab(TextView::new("Fooooo").with_name("So"))
+                    .with_bar_placement(placement)
+                    .with_borderless(borderless);
+                siv.add_layer(tabs);
+            });
+            tsiv.step();
+            let screen = tsiv.last_screen();
+            assert!(screen.find_occurences("Psh").is_empty());
+            let position = screen.find_occurences("Stonks")[0].min();
+            tsiv.input(Event::Mouse {
+                offset: Vec2::zero(),
+                position,
+                event: MouseEvent::Release(MouseButton::Left),
+            });
+            let screen = tsiv.last_screen();
+            assert_eq!(
+                screen.find_occurences("Psh").len(),
+                1,
+                "{:?} borderless: {}",
+                placement,
+                borderless
+            );
+        }
+    }
+}
+
+#[test]
+fn end2end_horizontal_bottom() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_bar_placement(Placement::HorizontalBottom);
+        siv.add_layer(tabs);
+    });
+    let screen = frames.try_iter().last().unwrap();
+    // The bar is cut into the bottom border instead of being placed below it
+    let bar = screen.find_occurences("Stonks")[0].min();
+    let corner = screen.find_occurences("└")[0].min();
+    assert_eq!(bar.y, corner.y);
+    assert_snapshot!(screen);
+}
+
+#[test]
+fn end2end_tab_widths() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much longer than the others"))
+            .with_tab_min_width(8)
+            .with_tab_max_width(Some(12))
+            .with_equal_tab_widths(true)
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Setting active tab has failed"));
+        siv.add_layer(tabs.with_name("panel"));
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    tsiv._call_on(|siv| {
+        siv.call_on_name("panel", |panel: &mut TabPanel| {
+            assert!(panel.is_tab_truncated("Much longer than the others"));
+            assert!(!panel.is_tab_truncated("So"));
+            assert_eq!(
+                panel.cursor_tab_title(),
+                Some("Much longer than the others")
+            );
+        });
+    });
+    assert_snapshot!(screen);
+}
+
+#[test]
+fn end2end_tab_widths_select() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("A rather long title"))
+            .with_tab(TextView::new("Fooooo").with_name("Another long title"))
+            .with_tab_max_width(Some(8));
+        siv.add_layer(tabs);
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    // Clicking the end of the truncated first tab must not select its neighbour
+    let position = screen.find_occurences("A rat…")[0].max();
+    tsiv.input(Event::Mouse {
+        offset: Vec2::zero(),
+        position,
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("Pshhhh").len(), 1);
+}
+
+#[test]
+fn end2end_align_stretch() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_bar_alignment(Align::Stretch);
+        siv.add_fullscreen_layer(tabs.full_screen());
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_align_space_between() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_bar_alignment(Align::SpaceBetween);
+        siv.add_fullscreen_layer(tabs.full_screen());
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_align_space_between_vertical() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_bar_alignment(Align::SpaceBetween)
+            .with_bar_placement(Placement::VerticalRight);
+        siv.add_fullscreen_layer(tabs.full_screen());
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_align_mouse_select() {
+    for align in [Align::Stretch, Align::SpaceBetween, Align::End] {
+        for placement in [Placement::HorizontalTop, Placement::VerticalLeft] {
+            let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+                let tabs = TabPanel::new()
+                    .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+                    .with_tab(TextView::new("Fooooo").with_name("So"))
+                    .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+                    .with_bar_alignment(align)
+                    .with_bar_placement(placement);
+                siv.add_fullscreen_layer(tabs.full_screen());
+            });
+            tsiv.step();
+            let screen = tsiv.last_screen();
+            let position = screen.find_occurences("So")[0].min();
+            tsiv.input(Event::Mouse {
+                offset: Vec2::zero(),
+                position,
+                event: MouseEvent::Release(MouseButton::Left),
+            });
+            let screen = tsiv.last_screen();
+            assert_eq!(
+                screen.find_occurences("Foo").len(),
+                1,
+                "{:?} {:?}",
+                align,
+                placement
+            );
+        }
+    }
+}
+
+#[test]
+fn end2end_separator_text() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab_separator(Separator::Text(" | ".into(), " # ".into()))
+            .with_tab_padding(0);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_separator_none() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab_separator(Separator::None)
+            .with_tab_padding(2);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_tab_gap() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab_gap(2);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_tab_gap_vertical() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab_gap(1)
+            .with_bar_placement(Placement::VerticalRight);
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[test]
+fn end2end_separator_mouse_select() {
+    let separators = [
+        Separator::Border,
+        Separator::Text(" | ".into(), " # ".into()),
+        Separator::None,
+    ];
+    for separator in separators {
+        for placement in [Placement::HorizontalTop, Placement::VerticalLeft] {
+            let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+                let tabs = TabPanel::new()
+                    .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+                    .with_tab(TextView::new("Fooooo").with_name("So"))
+                    .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+                    .with_tab_separator(separator.clone())
+                    .with_tab_padding(2)
+                    .with_tab_gap(1)
+                    .with_bar_placement(placement);
+                siv.add_fullscreen_layer(tabs.full_screen());
+            });
+            tsiv.step();
+            let screen = tsiv.last_screen();
+            // The padding in front of the title belongs to the tab as well
+            let position = screen.find_occurences("So")[0].min() - (2, 0);
+            tsiv.input(Event::Mouse {
+                offset: Vec2::zero(),
+                position,
+                event: MouseEvent::Release(MouseButton::Left),
+            });
+            let screen = tsiv.last_screen();
+            assert_eq!(
+                screen.find_occurences("Foo").len(),
+                1,
+                "{:?} {:?}",
+                separator,
+                placement
+            );
+        }
+    }
+}
+
+#[test]
+fn end2end_many_tabs_mouse_select() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::new();
+        for idx in 0..300 {
+            tabs.add_tab(TextView::new(format!("Content {}", idx)).with_name(format!("T{}", idx)));
+        }
+        siv.add_fullscreen_layer(tabs.full_screen());
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    let position = screen.find_occurences("T12 ")[0].min();
+    tsiv.input(Event::Mouse {
+        offset: Vec2::zero(),
+        position,
+        event: MouseEvent::Release(MouseButton::Left),
+    });
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("Content 12").len(), 1);
+}
+
+#[test]
+fn end2end_overflow() {
+    for placement in [Placement::HorizontalTop, Placement::VerticalLeft] {
+        let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+            let mut tabs = TabPanel::new().with_bar_placement(placement);
+            for idx in 0..30 {
+                tabs.add_tab(
+                    TextView::new(format!("Content {}", idx)).with_name(format!("T{}", idx)),
+                );
+            }
+            tabs.set_active_tab("T0").expect("Id not taken");
+            siv.add_fullscreen_layer(tabs.full_screen());
+        });
+        tsiv.step();
+        let screen = tsiv.last_screen();
+        let (position, marker) = if placement == Placement::HorizontalTop {
+            (Vec2::new(78, 0), "…")
+        } else {
+            (Vec2::new(4, 22), "⋮")
+        };
+        let cell = screen[position].as_ref().expect("Cell not drawn");
+        assert_eq!(cell.letter.unwrap(), marker);
+        // Clicking the marker does not select the tab below it
+        tsiv.input(Event::Mouse {
+            offset: Vec2::zero(),
+            position,
+            event: MouseEvent::Release(MouseButton::Left),
+        });
+        let screen = tsiv.last_screen();
+        assert_eq!(screen.find_occurences("Content 0").len(), 1);
+        assert_snapshot!(screen);
+    }
+}
+
+#[test]
+fn end2end_changes_before_start() {
+    let (frames, _) = setup_test_environment(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"));
+        // The bar follows every change, even before the first layout
+        tabs.set_active_tab("Stonks").expect("Id not taken");
+        tabs.swap_tabs("Stonks", "Much");
+        tabs.remove_tab("So").expect("Id not taken");
+        tabs.add_tab(TextView::new("Blaaah").with_name("Wow"));
+        tabs.set_active_tab("Stonks").expect("Id not taken");
+        siv.add_layer(tabs);
+    });
+    assert_snapshot!(frames.try_iter().last().unwrap());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Host {
+    Web(u32),
+    Db(u32),
+}
+
+#[test]
+fn end2end_keyed_tabs() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let mut tabs = TabPanel::default()
+            .with_keyed_tab(Host::Web(42), TextView::new("Serving").with_name("web"))
+            .with_keyed_tab(Host::Db(7), TextView::new("Replicating").with_name("db"));
+        tabs.set_tab_title(&Host::Web(42), "web-42 (3)")
+            .expect("Id not taken");
+        tabs.set_active_tab(&Host::Web(42)).expect("Id not taken");
+        siv.add_layer(tabs.with_name("tabs"));
+    });
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("web-42 (3)").len(), 1);
+    assert_eq!(screen.find_occurences("Serving").len(), 1);
+    tsiv.input(Event::Key(Key::Right));
+    tsiv.input(Event::Key(Key::Enter));
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("Replicating").len(), 1);
+    tsiv.siv.call_on_name("tabs", |tabs: &mut TabPanel<Host>| {
+        assert_eq!(tabs.active_tab(), Some(&Host::Db(7)));
+        assert_eq!(tabs.cursor_tab(), Some(&Host::Db(7)));
+        assert_eq!(tabs.cursor_tab_title(), Some("db"));
+        assert_eq!(tabs.tab_order(), vec![Host::Web(42), Host::Db(7)]);
+    });
+}
+
+#[test]
+fn end2end_unnamed_tabs() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Named metrics").with_name("Metrics"))
+            .with_tab_with_id("Logs".to_owned(), TextView::new("Unnamed logs"));
+        siv.add_fullscreen_layer(tabs.full_screen());
+        // The id of a tab does not collide with the names of other views
+        siv.add_layer(TextView::new("Elsewhere").with_name("Logs"));
+    });
+    tsiv.siv
+        .call_on_name("Logs", |view: &mut TextView| view.set_content("Found"))
+        .expect("Name not found");
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("Found").len(), 1);
+    assert_eq!(screen.find_occurences("Unnamed logs").len(), 1);
+}
+
+#[test]
+fn end2end_batch_update() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Old first").with_name("First"))
+            .with_tab(TextView::new("Old second").with_name("Second"));
+        siv.add_layer(tabs.with_name("tabs"));
+    });
+    tsiv.siv.call_on_name("tabs", |tabs: &mut TabPanel| {
+        tabs.update(|tabs| {
+            for id in tabs.tab_order() {
+                tabs.remove_tab(&id).expect("Id not taken");
+            }
+            tabs.add_tab(TextView::new("New first").with_name("First"));
+            tabs.add_tab(TextView::new("New second").with_name("Second"));
+            tabs.add_tab(TextView::new("Gone").with_name("Third"));
+            tabs.remove_tab("Third").expect("Id not taken");
+        })
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    // The tab active before the update is active again
+    assert_eq!(screen.find_occurences("New second").len(), 1);
+    assert_eq!(screen.find_occurences("Third").len(), 0);
+}
+
+#[test]
+fn end2end_replace_tab() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_active_tab("Stonks")
+            .unwrap_or_else(|_| panic!("Id not taken"));
+        siv.add_layer(tabs.with_name("tabs"));
+    });
+    tsiv.siv.call_on_name("tabs", |tabs: &mut TabPanel| {
+        tabs.replace_tab("Stonks", TextView::new("Replaced"))
+            .expect("Id not taken");
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    assert_eq!(screen.find_occurences("Replaced").len(), 1);
+    assert_eq!(screen.find_occurences("Stonks").len(), 1);
+    assert_eq!(screen.find_occurences("So").len(), 1);
+}
+
+#[test]
+fn end2end_sort_tabs() {
+    let mut tsiv = TestCursive::new(|siv: &mut cursive::Cursive| {
+        let tabs = TabPanel::new()
+            .with_tab(TextView::new("Pshhhh").with_name("Stonks"))
+            .with_tab(TextView::new("Fooooo").with_name("So"))
+            .with_tab(TextView::new("Ahhhhh").with_name("Much"))
+            .with_active_tab("So")
+            .unwrap_or_else(|_| panic!("Id not taken"));
+        siv.add_layer(tabs.with_name("tabs"));
+    });
+    tsiv.siv.call_on_name("tabs", |tabs: &mut TabPanel| {
+        tabs.sort_tabs_by_key(|(_, title)| title.to_owned());
+    });
+    tsiv.step();
+    let screen = tsiv.last_screen();
+    let x = |text: &str| screen.find_occurences(text)[0].min().x;
+    assert!(x("Much") < x("So") && x("So") < x("Stonks"));
+    assert_eq!(screen.find_occurences("Fooooo").len(), 1);
 }